@@ -0,0 +1,114 @@
+use ::base64::base64::{self, Base64Config, Decoder, Encoder, constant_time, parallel};
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+/// 16 B to 64 MiB, in steps of 16x
+const SIZES: [usize; 7] = [16, 256, 4 << 10, 64 << 10, 1 << 20, 16 << 20, 64 << 20];
+
+fn configs() -> [(&'static str, Base64Config); 3] {
+    [
+        ("standard", Base64Config::standard()),
+        ("url", Base64Config::url()),
+        ("mime", Base64Config::mime()),
+    ]
+}
+
+/// Deterministic bytes covering every symbol, so no config gets an easy input.
+fn input(size: usize) -> Vec<u8> {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    (0..size)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+fn threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Large inputs take long enough per iteration that fewer samples suffice.
+fn sample_size(size: usize) -> usize {
+    if size >= 1 << 20 { 10 } else { 100 }
+}
+
+fn bench_encode(c: &mut Criterion) {
+    for (name, config) in configs() {
+        let mut group = c.benchmark_group(format!("encode/{name}"));
+        for size in SIZES {
+            let bytes = input(size);
+            let mut output = vec![0u8; Encoder::max_output_len(size)];
+            group.throughput(Throughput::Bytes(size as u64));
+            group.sample_size(sample_size(size));
+            group.bench_with_input(BenchmarkId::new("iter", size), &bytes, |b, bytes| {
+                b.iter(|| Vec::from_iter(base64::encode(&config, black_box(bytes))))
+            });
+            group.bench_with_input(BenchmarkId::new("to_vec", size), &bytes, |b, bytes| {
+                b.iter(|| base64::encode_to_vec(&config, black_box(bytes)))
+            });
+            group.bench_with_input(BenchmarkId::new("to_slice", size), &bytes, |b, bytes| {
+                b.iter(|| base64::encode_to_slice(&config, black_box(bytes), &mut output))
+            });
+            group.bench_with_input(
+                BenchmarkId::new("constant_time", size),
+                &bytes,
+                |b, bytes| b.iter(|| constant_time::encode_to_vec(&config, black_box(bytes))),
+            );
+            group.bench_with_input(BenchmarkId::new("parallel", size), &bytes, |b, bytes| {
+                b.iter(|| parallel::encode_parallel(&config, black_box(bytes), threads()))
+            });
+        }
+        group.finish();
+    }
+}
+
+fn bench_decode(c: &mut Criterion) {
+    for (name, config) in configs() {
+        let mut group = c.benchmark_group(format!("decode/{name}"));
+        for size in SIZES {
+            let encoded = base64::encode_to_vec(&config, &input(size));
+            let mut output = vec![0u8; Decoder::max_output_len(encoded.len())];
+            // throughput in decoded bytes, comparable with the encode groups
+            group.throughput(Throughput::Bytes(size as u64));
+            group.sample_size(sample_size(size));
+            group.bench_with_input(BenchmarkId::new("iter", size), &encoded, |b, encoded| {
+                b.iter(|| Vec::from_iter(base64::decode(&config, black_box(encoded)).unwrap()))
+            });
+            group.bench_with_input(BenchmarkId::new("to_vec", size), &encoded, |b, encoded| {
+                b.iter(|| base64::decode_to_vec(&config, black_box(encoded)).unwrap())
+            });
+            group.bench_with_input(
+                BenchmarkId::new("to_slice", size),
+                &encoded,
+                |b, encoded| {
+                    b.iter(|| {
+                        base64::decode_to_slice(&config, black_box(encoded), &mut output).unwrap()
+                    })
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("constant_time", size),
+                &encoded,
+                |b, encoded| {
+                    b.iter(|| constant_time::decode_to_vec(&config, black_box(encoded)).unwrap())
+                },
+            );
+            group.bench_with_input(
+                BenchmarkId::new("parallel", size),
+                &encoded,
+                |b, encoded| {
+                    b.iter(|| {
+                        parallel::decode_parallel(&config, black_box(encoded), threads()).unwrap()
+                    })
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_encode, bench_decode);
+criterion_main!(benches);
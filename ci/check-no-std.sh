@@ -0,0 +1,17 @@
+#!/bin/sh
+# Builds the library the way firmware uses it: for a bare-metal target that
+# has no std, first with only core and then with alloc. The tests cannot run
+# there, so they run on the host in the same two configurations.
+set -eu
+
+target="${1:-thumbv7em-none-eabi}"
+
+if command -v rustup >/dev/null && [ "$target" != "$(rustc -vV | sed -n 's/^host: //p')" ]; then
+    rustup target add "$target"
+fi
+
+cargo build -p base64 --lib --no-default-features --target "$target"
+cargo build -p base64 --lib --no-default-features --features alloc --target "$target"
+
+cargo test -p base64 --lib --no-default-features
+cargo test -p base64 --lib --no-default-features --features alloc
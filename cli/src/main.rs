@@ -0,0 +1,700 @@
+use ::base64::{base64, base91, data_url, jwt, mime, pem, ssh, uuencode, yenc};
+use base64::{
+    Base64Config, Base64Error, Decoder, Encoder, constant_time, decode_to_vec, encode_to_vec,
+    parallel, secret,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use memmap2::Mmap;
+use std::error;
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use uuencode::{UuFile, UuVariant};
+
+#[derive(ValueEnum, Clone, Default)]
+enum Alphabet {
+    /// A-Z, a-z, 0-9, +, / with optional = padding
+    #[default]
+    Standard,
+    /// A-Z, a-z, 0-9, -, _ with optional = padding (URL/filename safe)
+    Url,
+    /// A-Z, a-z, 0-9, +, / with required = padding (MIME)
+    Mime,
+    /// basE91, 91 printable ASCII characters in 13/14-bit groups (~23% overhead)
+    Base91,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "base64",
+    about = "Encode or decode data using base64",
+    long_about = None
+)]
+struct Cli {
+    /// Decode input instead of encoding it (piped input can leave partial output on error)
+    #[arg(short, long)]
+    decode: bool,
+
+    /// Alphabet / encoding variant to use
+    #[arg(short, long, value_enum, default_value_t = Alphabet::Standard)]
+    alphabet: Alphabet,
+
+    /// Do not output a trailing newline
+    #[arg(short, long)]
+    no_newline: bool,
+
+    /// Read input from a file
+    #[arg(short, long, conflicts_with = "input")]
+    file: Option<std::path::PathBuf>,
+
+    /// Input string to encode or decode
+    #[arg(short, long, conflicts_with = "file")]
+    input: Option<String>,
+
+    /// Produce a data: URL with a MIME type sniffed from the input (with --decode, parse one)
+    #[arg(long, conflicts_with = "alphabet")]
+    data_url: bool,
+
+    /// Use the constant-time base64 codec, for private keys and tokens
+    #[arg(long, conflicts_with = "data_url")]
+    constant_time: bool,
+
+    /// Split base64 encoding or decoding of the input across this many threads
+    #[arg(long, default_value_t = 1, conflicts_with_all = ["constant_time", "data_url"])]
+    threads: usize,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Uuencode or xxencode a file with begin/end framing
+    Uuencode(UuencodeArgs),
+    /// Encode a file with yEnc, or decode and reassemble yEnc part files
+    Yenc(YencArgs),
+    /// Decode and inspect a JSON Web Token without verifying it online
+    Jwt(JwtArgs),
+    /// List and extract the base64 encoded parts of a MIME message (.eml)
+    Mime(MimeArgs),
+    /// Show SHA256 fingerprints of OpenSSH public keys or an authorized_keys file
+    SshFingerprint(SshFingerprintArgs),
+    /// Read or write PEM armored data
+    Pem {
+        #[command(subcommand)]
+        action: PemCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PemCommand {
+    /// Wrap binary input in a PEM block
+    Encode(PemEncodeArgs),
+    /// Extract the binary contents of PEM blocks
+    Decode(PemDecodeArgs),
+}
+
+#[derive(Args)]
+struct PemEncodeArgs {
+    /// Label for the BEGIN/END lines, e.g. CERTIFICATE
+    #[arg(short, long)]
+    label: String,
+
+    /// Read input from a file instead of stdin
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct PemDecodeArgs {
+    /// Only decode blocks with this label
+    #[arg(short, long)]
+    label: Option<String>,
+
+    /// Read input from a file instead of stdin
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct UuencodeArgs {
+    /// Decode a framed file instead of encoding one
+    #[arg(short, long)]
+    decode: bool,
+
+    /// Use the xxencode alphabet instead of uuencode
+    #[arg(short, long)]
+    xx: bool,
+
+    /// Read input from a file instead of stdin
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// File name for the begin line (defaults to the input file name)
+    #[arg(long)]
+    name: Option<String>,
+
+    /// Octal permissions for the begin line (defaults to those of the input file)
+    #[arg(long, value_parser = parse_octal_mode)]
+    mode: Option<u32>,
+
+    /// Write decoded data here instead of the name from the begin line ("-" for stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct YencArgs {
+    /// Decode and reassemble part files instead of encoding
+    #[arg(short, long)]
+    decode: bool,
+
+    /// Write decoded data here instead of the name from the header ("-" for stdout)
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// File to encode, or part files to decode (stdin if omitted)
+    files: Vec<PathBuf>,
+}
+
+#[derive(Args)]
+struct JwtArgs {
+    /// Token to inspect (read from stdin if omitted)
+    token: Option<String>,
+
+    /// Verify an HS256 signature with the secret in this file (one trailing newline is ignored)
+    #[arg(short, long)]
+    secret_file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct MimeArgs {
+    /// Message file to read (stdin if omitted)
+    file: Option<PathBuf>,
+
+    /// Write each decoded part into this directory
+    #[arg(short, long)]
+    output_dir: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SshFingerprintArgs {
+    /// Public key or authorized_keys file to read (stdin if omitted)
+    file: Option<PathBuf>,
+}
+
+fn parse_octal_mode(mode: &str) -> Result<u32, String> {
+    u32::from_str_radix(mode, 8).map_err(|e| format!("invalid octal mode: {e}"))
+}
+
+fn base64_config(alphabet: &Alphabet) -> Option<Base64Config> {
+    match alphabet {
+        Alphabet::Standard => Some(Base64Config::standard()),
+        Alphabet::Url => Some(Base64Config::url()),
+        Alphabet::Mime => Some(Base64Config::mime()),
+        Alphabet::Base91 => None,
+    }
+}
+
+fn encode_input(alphabet: &Alphabet, constant_time: bool, threads: usize, input: &[u8]) -> Vec<u8> {
+    match base64_config(alphabet) {
+        Some(config) if constant_time => constant_time::encode_to_vec(&config, input),
+        Some(config) if threads > 1 => parallel::encode_parallel(&config, input, threads),
+        Some(config) => encode_to_vec(&config, input),
+        None => base91::encode_to_vec(input),
+    }
+}
+
+fn decode_input(
+    alphabet: &Alphabet,
+    constant_time: bool,
+    threads: usize,
+    input: &[u8],
+) -> Result<Box<dyn AsRef<[u8]>>, Box<dyn error::Error>> {
+    match base64_config(alphabet) {
+        Some(config) if constant_time => Ok(Box::new(secret::decode_secret(&config, input)?)),
+        Some(config) if threads > 1 => Ok(Box::new(parallel::decode_parallel(
+            &config, input, threads,
+        )?)),
+        Some(config) => Ok(Box::new(decode_to_vec(&config, input)?)),
+        None => Ok(Box::new(base91::decode_to_vec(input)?)),
+    }
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("base64: {message}");
+    std::process::exit(1);
+}
+
+fn read_input(file: Option<&Path>, input: Option<&str>) -> Vec<u8> {
+    if let Some(path) = file {
+        std::fs::read(path).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)))
+    } else if let Some(s) = input {
+        s.as_bytes().to_vec()
+    } else {
+        let mut buf = Vec::new();
+        io::stdin()
+            .read_to_end(&mut buf)
+            .unwrap_or_else(|e| fail(e));
+        buf
+    }
+}
+
+/// Bytes read per step when streaming, small enough to stay in cache.
+const STREAM_BLOCK_SIZE: usize = 64 * 1024;
+/// Output is collected into writes of this size.
+const OUTPUT_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// Input to the top-level encode/decode. Regular files are mapped instead
+/// of read, so they are never copied into memory as a whole.
+enum Input {
+    Mapped(Mmap),
+    Bytes(Vec<u8>),
+    /// Pipes, terminals and anything else that cannot be mapped
+    Stream(Box<dyn Read>),
+}
+
+impl Input {
+    fn open(file: Option<&Path>, input: Option<&str>) -> Input {
+        match (file, input) {
+            (Some(path), _) => {
+                Input::map(path).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)))
+            }
+            (None, Some(s)) => Input::Bytes(s.as_bytes().to_vec()),
+            (None, None) => Input::Stream(Box::new(io::stdin().lock())),
+        }
+    }
+
+    fn map(path: &Path) -> io::Result<Input> {
+        let file = File::open(path)?;
+        if !file.metadata()?.is_file() {
+            return Ok(Input::Stream(Box::new(file)));
+        }
+        // SAFETY: the map is read-only; as with any tool that maps its
+        // input, truncating the file while it is being read is not supported
+        let map = unsafe { Mmap::map(&file)? };
+        // only a hint that read-ahead pays off, so failing is fine
+        #[cfg(unix)]
+        map.advise(memmap2::Advice::Sequential).ok();
+        Ok(Input::Mapped(map))
+    }
+
+    /// The whole input, for the codecs that cannot work block by block.
+    fn into_bytes(self) -> io::Result<Box<dyn AsRef<[u8]>>> {
+        match self {
+            Input::Mapped(map) => Ok(Box::new(map)),
+            Input::Bytes(bytes) => Ok(Box::new(bytes)),
+            Input::Stream(mut reader) => {
+                let mut buf = Vec::new();
+                reader.read_to_end(&mut buf)?;
+                Ok(Box::new(buf))
+            }
+        }
+    }
+
+    /// The input if it is already in memory or mapped, so it can be read twice.
+    fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Input::Mapped(map) => Some(map),
+            Input::Bytes(bytes) => Some(bytes),
+            Input::Stream(_) => None,
+        }
+    }
+
+    fn into_reader(self) -> Box<dyn Read> {
+        match self {
+            Input::Mapped(map) => Box::new(io::Cursor::new(map)),
+            Input::Bytes(bytes) => Box::new(io::Cursor::new(bytes)),
+            Input::Stream(reader) => reader,
+        }
+    }
+}
+
+/// Reads the next block, retrying interrupted reads. Returns 0 at the end.
+fn read_block(input: &mut impl Read, block: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match input.read(block) {
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }
+}
+
+fn encode_stream(
+    config: &Base64Config,
+    mut input: impl Read,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut block = vec![0u8; STREAM_BLOCK_SIZE];
+    let mut encoded = vec![0u8; Encoder::max_output_len(STREAM_BLOCK_SIZE)];
+    let mut encoder = Encoder::new(config);
+    loop {
+        let length = read_block(&mut input, &mut block)?;
+        if length == 0 {
+            break;
+        }
+        let written = encoder.update(&block[..length], &mut encoded);
+        out.write_all(&encoded[..written])?;
+    }
+    let (tail, length) = encoder.finish();
+    out.write_all(&tail[..length])
+}
+
+/// Decodes block by block. Output that has already been written stays
+/// written when an error turns up further into the input; callers that can
+/// read the input twice validate it into [`io::sink`] first.
+fn decode_stream(
+    config: &Base64Config,
+    mut input: impl Read,
+    out: &mut impl Write,
+) -> io::Result<Result<(), Base64Error>> {
+    let mut block = vec![0u8; STREAM_BLOCK_SIZE];
+    let mut decoded = vec![0u8; Decoder::max_output_len(STREAM_BLOCK_SIZE)];
+    let mut decoder = Decoder::new(config);
+    loop {
+        let length = read_block(&mut input, &mut block)?;
+        if length == 0 {
+            break;
+        }
+        match decoder.update(&block[..length], &mut decoded) {
+            Ok(written) => out.write_all(&decoded[..written])?,
+            Err(e) => return Ok(Err(e)),
+        }
+    }
+    match decoder.finish() {
+        Ok((tail, length)) => out.write_all(&tail[..length])?,
+        Err(e) => return Ok(Err(e)),
+    }
+    Ok(Ok(()))
+}
+
+/// Resolves where to write a decoded file whose name came from inside the encoded data.
+fn output_path(output: Option<&Path>, embedded_name: &str) -> PathBuf {
+    match output {
+        Some(output) => output.to_path_buf(),
+        None if embedded_name == "-" || embedded_name == "/dev/stdout" => PathBuf::from("-"),
+        // never let the embedded name direct output outside the current directory
+        None => match Path::new(embedded_name).file_name() {
+            Some(name) => PathBuf::from(name),
+            None => fail(format!("invalid file name \"{embedded_name}\"")),
+        },
+    }
+}
+
+fn write_output(path: &Path, bytes: &[u8], mode: Option<u32>) -> io::Result<()> {
+    if path == Path::new("-") || path == Path::new("/dev/stdout") {
+        return io::stdout().lock().write_all(bytes);
+    }
+    std::fs::write(path, bytes)?;
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode & 0o777))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    Ok(())
+}
+
+fn file_mode(path: &Path) -> Option<u32> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(metadata.permissions().mode() & 0o777)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+fn run_uuencode(args: &UuencodeArgs) {
+    let variant = if args.xx {
+        UuVariant::Xxencode
+    } else {
+        UuVariant::Uuencode
+    };
+    let input = read_input(args.file.as_deref(), None);
+
+    if args.decode {
+        let file = uuencode::decode_file(variant, &input)
+            .unwrap_or_else(|e| fail(format!("decode error: {e}")));
+        let output = output_path(args.output.as_deref(), &file.name);
+        write_output(&output, &file.data, Some(file.mode))
+            .unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)));
+    } else {
+        let name = match (&args.name, &args.file) {
+            (Some(name), _) => name.clone(),
+            (None, Some(path)) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "-".to_string()),
+            (None, None) => "-".to_string(),
+        };
+        let mut file = UuFile::new(name, input);
+        if let Some(mode) = args.mode.or(args.file.as_deref().and_then(file_mode)) {
+            file.mode = mode;
+        }
+        let encoded = uuencode::encode_file(variant, &file);
+        io::stdout()
+            .lock()
+            .write_all(&encoded)
+            .unwrap_or_else(|e| fail(e));
+    }
+}
+
+fn run_yenc(args: &YencArgs) {
+    if args.decode {
+        let inputs = match args.files.as_slice() {
+            [] => vec![read_input(None, None)],
+            files => files.iter().map(|f| read_input(Some(f), None)).collect(),
+        };
+        let parts = inputs
+            .iter()
+            .map(|input| yenc::decode_part(input))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| fail(format!("decode error: {e}")));
+        let file = yenc::reassemble(parts).unwrap_or_else(|e| fail(format!("decode error: {e}")));
+        let output = output_path(args.output.as_deref(), &file.name);
+        write_output(&output, &file.data, None)
+            .unwrap_or_else(|e| fail(format!("{}: {}", output.display(), e)));
+    } else {
+        let (input, name) = match args.files.as_slice() {
+            [] => (read_input(None, None), "-".to_string()),
+            [file] => (
+                read_input(Some(file), None),
+                file.file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "-".to_string()),
+            ),
+            _ => fail("yenc: encoding takes a single file"),
+        };
+        let encoded = yenc::encode_file(&name, &input);
+        io::stdout()
+            .lock()
+            .write_all(&encoded)
+            .unwrap_or_else(|e| fail(e));
+    }
+}
+
+fn run_pem(action: &PemCommand) {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    match action {
+        PemCommand::Encode(args) => {
+            let input = read_input(args.file.as_deref(), None);
+            let block = pem::PemBlock::new(&args.label, input);
+            out.write_all(&pem::encode(&block))
+                .unwrap_or_else(|e| fail(e));
+        }
+        PemCommand::Decode(args) => {
+            let input = read_input(args.file.as_deref(), None);
+            let blocks = pem::parse(&input).unwrap_or_else(|e| fail(format!("decode error: {e}")));
+            let mut matching = blocks
+                .iter()
+                .filter(|block| {
+                    args.label
+                        .as_ref()
+                        .is_none_or(|label| *label == block.label)
+                })
+                .peekable();
+            if matching.peek().is_none() {
+                fail("decode error: no matching PEM block found");
+            }
+            for block in matching {
+                out.write_all(&block.data).unwrap_or_else(|e| fail(e));
+            }
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn run_jwt(args: &JwtArgs) {
+    let token = read_input(None, args.token.as_deref());
+    let jwt = jwt::parse(&String::from_utf8_lossy(&token))
+        .unwrap_or_else(|e| fail(format!("decode error: {e}")));
+
+    let pretty = |value: &serde_json::Value| serde_json::to_string_pretty(value).unwrap();
+    println!("Header:\n{}", pretty(&jwt.header));
+    match jwt.payload_json() {
+        Some(payload) => println!("Payload:\n{}", pretty(&payload)),
+        None => println!(
+            "Payload (not JSON):\n{}",
+            String::from_utf8_lossy(&jwt.payload)
+        ),
+    }
+    println!(
+        "Signature ({} bytes):\n{}",
+        jwt.signature.len(),
+        to_hex(&jwt.signature)
+    );
+    if jwt.is_unsecured() {
+        eprintln!("base64: warning: token uses \"alg\": \"none\" and is not signed");
+    }
+
+    if let Some(path) = &args.secret_file {
+        let secret =
+            std::fs::read(path).unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)));
+        let secret = secret
+            .strip_suffix(b"\n")
+            .map(|s| s.strip_suffix(b"\r").unwrap_or(s))
+            .unwrap_or(&secret);
+        match jwt.verify_hs256(secret) {
+            Ok(true) => println!("Signature: valid"),
+            Ok(false) => fail("signature: INVALID"),
+            Err(e) => fail(e),
+        }
+    }
+}
+
+fn run_mime(args: &MimeArgs) {
+    let message = read_input(args.file.as_deref(), None);
+    let parts = mime::base64_parts(&message).unwrap_or_else(|e| fail(format!("decode error: {e}")));
+    for (i, part) in parts.iter().enumerate() {
+        let name = part.file_name().map_or_else(
+            || PathBuf::from(format!("part-{}.bin", i + 1)),
+            PathBuf::from,
+        );
+        println!(
+            "{}\t{}\t{}\t{} bytes",
+            i + 1,
+            part.content_type,
+            name.display(),
+            part.data.len()
+        );
+        if let Some(dir) = &args.output_dir {
+            let path = dir.join(&name);
+            write_output(&path, &part.data, None)
+                .unwrap_or_else(|e| fail(format!("{}: {}", path.display(), e)));
+        }
+    }
+}
+
+fn run_ssh_fingerprint(args: &SshFingerprintArgs) {
+    let input = read_input(args.file.as_deref(), None);
+    let mut failed = false;
+    for (line, key) in ssh::parse_authorized_keys(&String::from_utf8_lossy(&input)) {
+        match key {
+            Ok(key) => println!(
+                "{} {} {} ({})",
+                key.bits(),
+                key.fingerprint(),
+                key.comment.as_deref().unwrap_or("no comment"),
+                key.label()
+            ),
+            Err(e) => {
+                eprintln!("base64: line {line}: {e}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Some(command) = &cli.command {
+        match command {
+            Command::Uuencode(args) => run_uuencode(args),
+            Command::Yenc(args) => run_yenc(args),
+            Command::Jwt(args) => run_jwt(args),
+            Command::Mime(args) => run_mime(args),
+            Command::SshFingerprint(args) => run_ssh_fingerprint(args),
+            Command::Pem { action } => run_pem(action),
+        }
+        return;
+    }
+
+    if cli.constant_time && base64_config(&cli.alphabet).is_none() {
+        fail("--constant-time is only available for the base64 alphabets");
+    }
+    if cli.threads > 1 && base64_config(&cli.alphabet).is_none() {
+        fail("--threads is only available for the base64 alphabets");
+    }
+
+    let input = Input::open(cli.file.as_deref(), cli.input.as_deref());
+
+    let mut out = BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, io::stdout().lock());
+
+    let streamable = !cli.data_url && !cli.constant_time && cli.threads <= 1;
+    if let Some(config) = base64_config(&cli.alphabet).filter(|_| streamable) {
+        // a validation pass over a mapped file costs a second read, not memory
+        if cli.decode
+            && let Some(bytes) = input.as_bytes()
+        {
+            match decode_stream(&config, bytes, &mut io::sink()) {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => fail(format!("decode error: {e}")),
+                Err(e) => fail(e),
+            }
+        }
+        let reader = input.into_reader();
+        let result = if cli.decode {
+            decode_stream(&config, reader, &mut out)
+        } else {
+            encode_stream(&config, reader, &mut out).map(Ok)
+        };
+        // flush before failing, so that everything decoded up to an error is
+        // written rather than however much happened to fill the buffer
+        match result.and_then(|decoded| out.flush().map(|()| decoded)) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => fail(format!("decode error: {e}")),
+            Err(e) => fail(e),
+        }
+        if !cli.no_newline {
+            out.write_all(b"\n").ok();
+        }
+        out.flush().unwrap_or_else(|e| fail(e));
+        return;
+    }
+
+    let bytes = input.into_bytes().unwrap_or_else(|e| fail(e));
+    let input = (*bytes).as_ref();
+
+    if cli.decode {
+        let decoded = if cli.data_url {
+            data_url::parse(&String::from_utf8_lossy(input))
+                .map(|url| Box::new(url.data) as Box<dyn AsRef<[u8]>>)
+                .map_err(|e| e.into())
+        } else {
+            decode_input(&cli.alphabet, cli.constant_time, cli.threads, input)
+        };
+        match decoded {
+            Ok(bytes) => {
+                out.write_all((*bytes).as_ref()).unwrap_or_else(|e| {
+                    eprintln!("base64: {e}");
+                    std::process::exit(1);
+                });
+                if !cli.no_newline {
+                    out.write_all(b"\n").ok();
+                }
+            }
+            Err(e) => {
+                eprintln!("base64: decode error: {e}");
+                std::process::exit(1);
+            }
+        }
+    } else {
+        let encoded = if cli.data_url {
+            data_url::encode(data_url::sniff_mime_type(input), input).into_bytes()
+        } else {
+            encode_input(&cli.alphabet, cli.constant_time, cli.threads, input)
+        };
+        out.write_all(&encoded).unwrap_or_else(|e| {
+            eprintln!("base64: {e}");
+            std::process::exit(1);
+        });
+        if !cli.no_newline {
+            out.write_all(b"\n").ok();
+        }
+    }
+    out.flush().unwrap_or_else(|e| fail(e));
+}
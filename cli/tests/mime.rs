@@ -0,0 +1,38 @@
+use std::process::Command;
+
+const MESSAGE: &str = "Content-Type: multipart/mixed; boundary=b\r
+\r
+--b\r
+Content-Type: text/plain\r
+Content-Transfer-Encoding: base64\r
+Content-Disposition: attachment; filename=\"..\"\r
+\r
+SGk=\r
+--b--\r
+";
+
+#[test]
+fn test_mime_writes_a_part_named_dot_dot_under_a_fallback_name() {
+    let dir = std::env::temp_dir().join(format!("base64-mime-{}", std::process::id()));
+    let output_dir = dir.join("out");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let message = dir.join("message.eml");
+    std::fs::write(&message, MESSAGE).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_base64"))
+        .arg("mime")
+        .arg(&message)
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(output.stdout, b"1\ttext/plain\tpart-1.bin\t2 bytes\n");
+    assert_eq!(std::fs::read(output_dir.join("part-1.bin")).unwrap(), b"Hi");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,42 @@
+#![no_main]
+
+use base64::base64::{constant_time, decode, decode_to_vec, encode_to_vec, parallel};
+use base64_fuzz::FuzzConfig;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (FuzzConfig, Vec<u8>)| {
+    let (config, data) = input;
+    let Some(config) = config.build() else {
+        return;
+    };
+
+    // every decoder accepts exactly the same inputs, with the same output
+    let decoded = decode_to_vec(&config, &data);
+    let expected = decoded.as_ref().ok();
+    assert_eq!(
+        decode(&config, &data).map(Vec::from_iter).ok().as_ref(),
+        expected
+    );
+    assert_eq!(
+        constant_time::decode_to_vec(&config, &data).ok().as_ref(),
+        expected
+    );
+    assert_eq!(
+        format!("{:?}", parallel::decode_parallel(&config, &data, 3)),
+        format!("{decoded:?}")
+    );
+
+    // re-encoding what was accepted gives an input that decodes the same
+    if let Ok(decoded) = &decoded {
+        assert_eq!(
+            &decode_to_vec(&config, &encode_to_vec(&config, decoded)).unwrap(),
+            decoded
+        );
+    }
+
+    // and the same bytes taken as plain data survive a round trip
+    let encoded = encode_to_vec(&config, &data);
+    assert_eq!(constant_time::encode_to_vec(&config, &data), encoded);
+    assert_eq!(parallel::encode_parallel(&config, &data, 3), encoded);
+    assert_eq!(decode_to_vec(&config, &encoded).unwrap(), data);
+});
@@ -0,0 +1,67 @@
+#![no_main]
+
+use base64::base64::{Decoder, Encoder, decode_to_vec, encode_to_vec};
+use base64_fuzz::FuzzConfig;
+use libfuzzer_sys::fuzz_target;
+
+/// Cuts `data` at the given offsets, wrapped to its length.
+fn pieces<'a>(data: &'a [u8], cuts: &[u16]) -> Vec<&'a [u8]> {
+    let mut offsets: Vec<usize> = cuts
+        .iter()
+        .map(|c| *c as usize % (data.len() + 1))
+        .collect();
+    offsets.sort();
+    offsets.push(data.len());
+    let mut start = 0;
+    offsets
+        .into_iter()
+        .map(|end| {
+            let piece = &data[start..end];
+            start = end;
+            piece
+        })
+        .collect()
+}
+
+fuzz_target!(|input: (FuzzConfig, Vec<u8>, Vec<u16>)| {
+    let (config, data, cuts) = input;
+    let Some(config) = config.build() else {
+        return;
+    };
+
+    // feeding the encoder in pieces gives the one-shot output
+    let mut encoder = Encoder::new(&config);
+    let mut encoded = Vec::new();
+    for piece in pieces(&data, &cuts) {
+        let mut output = vec![0u8; Encoder::max_output_len(piece.len())];
+        let length = encoder.update(piece, &mut output);
+        encoded.extend_from_slice(&output[..length]);
+    }
+    let (tail, length) = encoder.finish();
+    encoded.extend_from_slice(&tail[..length]);
+    assert_eq!(encoded, encode_to_vec(&config, &data));
+
+    // and so does the decoder, including which error it reports
+    let mut decoder = Decoder::new(&config);
+    let mut decoded = Vec::new();
+    let mut result = Ok(());
+    for piece in pieces(&data, &cuts) {
+        let mut output = vec![0u8; Decoder::max_output_len(piece.len())];
+        match decoder.update(piece, &mut output) {
+            Ok(length) => decoded.extend_from_slice(&output[..length]),
+            Err(e) => {
+                result = Err(e);
+                break;
+            }
+        }
+    }
+    let result = result.and_then(|()| {
+        let (tail, length) = decoder.finish()?;
+        decoded.extend_from_slice(&tail[..length]);
+        Ok(decoded)
+    });
+    assert_eq!(
+        format!("{result:?}"),
+        format!("{:?}", decode_to_vec(&config, &data))
+    );
+});
@@ -0,0 +1,83 @@
+use arbitrary::Arbitrary;
+use base64::base64::{Base64Config, Padding};
+use std::ops::RangeInclusive;
+
+#[derive(Arbitrary, Debug, Clone, Copy)]
+pub enum FuzzPadding {
+    Required(u8),
+    Optional(u8),
+    NoPadding,
+}
+
+/// A config from arbitrary ranges, most of which `Base64Config::new` rejects,
+/// or a valid one so that decoding gets exercised either way.
+#[derive(Arbitrary, Debug)]
+pub enum FuzzConfig {
+    Ranges(Vec<(u8, u8)>, FuzzPadding),
+    /// 64 bytes shuffled out of all 256 by the seed, in seed order, with the
+    /// 65th as the pad byte if padding is `Some(required)`
+    Shuffled(Vec<u8>, Option<bool>),
+    Standard,
+    Url(FuzzPadding),
+    Mime,
+}
+
+/// Collapses runs of consecutive bytes, so alphabets get ranges of all sizes.
+fn ranges(alphabet: &[u8]) -> Vec<RangeInclusive<u8>> {
+    let mut ranges: Vec<RangeInclusive<u8>> = Vec::new();
+    for b in alphabet {
+        match ranges.last_mut() {
+            Some(r) if *r.end() as u16 + 1 == *b as u16 => *r = *r.start()..=*b,
+            _ => ranges.push(*b..=*b),
+        }
+    }
+    ranges
+}
+
+fn padding(padding: FuzzPadding) -> Padding {
+    match padding {
+        FuzzPadding::Required(p) => Padding::Required(p),
+        FuzzPadding::Optional(p) => Padding::Optional(p),
+        FuzzPadding::NoPadding => Padding::NoPadding,
+    }
+}
+
+impl FuzzConfig {
+    /// Builds the config, or `None` if it is invalid.
+    pub fn build(&self) -> Option<Base64Config> {
+        match self {
+            FuzzConfig::Ranges(pairs, pad) => {
+                let ranges: Vec<RangeInclusive<u8>> = pairs.iter().map(|(s, e)| *s..=*e).collect();
+                new_and_from_ranges(ranges, *pad)
+            }
+            FuzzConfig::Shuffled(seed, pad) => {
+                let mut bytes: Vec<u8> = (0..=255).collect();
+                for (i, s) in seed.iter().take(65).enumerate() {
+                    bytes.swap(i, i + *s as usize % (256 - i));
+                }
+                let padding = match pad {
+                    Some(true) => FuzzPadding::Required(bytes[64]),
+                    Some(false) => FuzzPadding::Optional(bytes[64]),
+                    None => FuzzPadding::NoPadding,
+                };
+                let config = new_and_from_ranges(ranges(&bytes[..64]), padding);
+                assert!(config.is_some(), "64 distinct bytes are a valid alphabet");
+                config
+            }
+            FuzzConfig::Standard => Some(Base64Config::standard()),
+            FuzzConfig::Url(pad) => Base64Config::url().with_padding(padding(*pad)).ok(),
+            FuzzConfig::Mime => Some(Base64Config::mime()),
+        }
+    }
+}
+
+/// Builds the config both ways and checks they agree.
+fn new_and_from_ranges(ranges: Vec<RangeInclusive<u8>>, pad: FuzzPadding) -> Option<Base64Config> {
+    let const_config = Base64Config::from_ranges(&ranges, padding(pad));
+    let config = Base64Config::new(ranges, padding(pad));
+    assert_eq!(
+        format!("{:?}", config.as_ref().err()),
+        format!("{:?}", const_config.as_ref().err()),
+    );
+    config.ok()
+}
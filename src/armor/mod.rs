@@ -0,0 +1,170 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use std::error;
+
+const LINE_WIDTH: usize = 64;
+const CRC24_INIT: u32 = 0xB7_04CE;
+const CRC24_POLY: u32 = 0x186_4CFB;
+const CRC24_MASK: u32 = 0xFF_FFFF;
+
+pub struct Armor {
+    /// The text between `-----BEGIN ` and `-----`, e.g. `PGP MESSAGE`
+    pub label: String,
+    /// Armor headers such as `Version` or `Comment`, in order of appearance
+    pub headers: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum ArmorError {
+    InvalidUtf8,
+    MissingBeginLine,
+    MissingEndLine(String),
+    MismatchedLabels(String, String),
+    InvalidHeader(String),
+    InvalidBase64(Base64Error),
+    InvalidChecksumLine(String),
+    ChecksumMismatch(u32, u32),
+}
+
+impl error::Error for ArmorError {}
+
+impl std::fmt::Display for ArmorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArmorError::InvalidUtf8 => write!(f, "Armored input is not valid UTF-8"),
+            ArmorError::MissingBeginLine => write!(f, "No \"-----BEGIN PGP\" line found"),
+            ArmorError::MissingEndLine(label) => {
+                write!(f, "Missing \"-----END {}-----\" line", label)
+            }
+            ArmorError::MismatchedLabels(begin, end) => {
+                write!(
+                    f,
+                    "BEGIN label \"{}\" does not match END label \"{}\"",
+                    begin, end
+                )
+            }
+            ArmorError::InvalidHeader(line) => write!(f, "Invalid armor header \"{}\"", line),
+            ArmorError::InvalidBase64(e) => write!(f, "Invalid base64: {}", e),
+            ArmorError::InvalidChecksumLine(line) => {
+                write!(f, "Invalid checksum line \"{}\"", line)
+            }
+            ArmorError::ChecksumMismatch(expected, actual) => {
+                write!(
+                    f,
+                    "CRC-24 mismatch: armor says {:06X}, data has {:06X}",
+                    *expected, *actual
+                )
+            }
+        }
+    }
+}
+
+/// CRC-24 as specified for the OpenPGP armor checksum (RFC 4880 section 6.1).
+pub fn crc24(bytes: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    for b in bytes {
+        crc ^= (*b as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & CRC24_MASK
+}
+
+fn decode_checksum(line: &str) -> Result<u32, ArmorError> {
+    let invalid = || ArmorError::InvalidChecksumLine(line.to_string());
+    let checksum = line.strip_prefix('=').ok_or_else(invalid)?;
+    let bytes =
+        decode_to_vec(&Base64Config::standard(), checksum.as_bytes()).map_err(|_| invalid())?;
+    match bytes[..] {
+        [b0, b1, b2] => Ok(u32::from_be_bytes([0, b0, b1, b2])),
+        _ => Err(invalid()),
+    }
+}
+
+fn encode_checksum(crc: u32) -> Vec<u8> {
+    let mut line = vec![b'='];
+    line.extend(encode_to_vec(
+        &Base64Config::standard(),
+        &crc.to_be_bytes()[1..],
+    ));
+    line
+}
+
+/// Decodes the first armored block in `bytes`, verifying its checksum if present.
+pub fn decode(bytes: &[u8]) -> Result<Armor, ArmorError> {
+    let text = std::str::from_utf8(bytes).map_err(|_| ArmorError::InvalidUtf8)?;
+    let mut lines = text.lines().map(str::trim);
+    let label = lines
+        .by_ref()
+        .find_map(|line| line.strip_prefix("-----BEGIN ")?.strip_suffix("-----"))
+        .ok_or(ArmorError::MissingBeginLine)?;
+    let missing_end = || ArmorError::MissingEndLine(label.to_string());
+
+    let mut lines = lines.peekable();
+    let mut headers = Vec::new();
+    // headers are optional, but when present they end with a blank line
+    while let Some(line) = lines.next_if(|line| line.contains(':')) {
+        let (key, value) = line
+            .split_once(": ")
+            .ok_or_else(|| ArmorError::InvalidHeader(line.to_string()))?;
+        headers.push((key.to_string(), value.to_string()));
+    }
+    lines.next_if(|line| line.is_empty());
+
+    let mut base64_encoded_bytes = Vec::new();
+    let mut checksum = None;
+    let end_label = loop {
+        let line = lines.next().ok_or_else(missing_end)?;
+        if let Some(end_label) = line
+            .strip_prefix("-----END ")
+            .and_then(|line| line.strip_suffix("-----"))
+        {
+            break end_label;
+        }
+        if line.starts_with('=') {
+            checksum = Some(decode_checksum(line)?);
+        } else {
+            base64_encoded_bytes.extend(line.bytes());
+        }
+    };
+    if end_label != label {
+        return Err(ArmorError::MismatchedLabels(
+            label.to_string(),
+            end_label.to_string(),
+        ));
+    }
+    let data = decode_to_vec(&Base64Config::standard(), &base64_encoded_bytes)
+        .map_err(ArmorError::InvalidBase64)?;
+    if let Some(expected) = checksum
+        && expected != crc24(&data)
+    {
+        return Err(ArmorError::ChecksumMismatch(expected, crc24(&data)));
+    }
+    Ok(Armor {
+        label: label.to_string(),
+        headers,
+        data,
+    })
+}
+
+pub fn encode(armor: &Armor) -> Vec<u8> {
+    let mut out = format!("-----BEGIN {}-----\n", armor.label).into_bytes();
+    for (key, value) in &armor.headers {
+        out.extend(format!("{key}: {value}\n").into_bytes());
+    }
+    out.push(b'\n');
+    for line in encode_to_vec(&Base64Config::standard(), &armor.data).chunks(LINE_WIDTH) {
+        out.extend(line);
+        out.push(b'\n');
+    }
+    out.extend(encode_checksum(crc24(&armor.data)));
+    out.extend(format!("\n-----END {}-----\n", armor.label).into_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,158 @@
+use super::*;
+
+// `gpg --armor --symmetric --compress-algo none` of "Hello, armor!\n" (GnuPG 2.2.40)
+const GPG_MESSAGE: &str = "-----BEGIN PGP MESSAGE-----
+
+jA0ECQMCqqvp/RB2IgP/0kQBeM3IOCTI5x+y9kv2Y1opjImByCgpMWMWnonu1LiD
+yGSFZEd9Zri/kuYsmk/Vamq4gaZnI9ozAaN4xkAiJFFkoX81ow==
+=XSWy
+-----END PGP MESSAGE-----
+";
+
+// `gpg --armor --comment "Armor test key" --export` of an ed25519 key (GnuPG 2.2.40)
+const GPG_PUBLIC_KEY: &str = "-----BEGIN PGP PUBLIC KEY BLOCK-----
+Comment: Armor test key
+
+mDMEatTpqBYJKwYBBAHaRw8BAQdAtXsRvDl6EiEZFcYgs/+/ipiz18PhrXnsZXpI
+dzL+dvm0HkFybW9yIFRlc3QgPGFybW9yQGV4YW1wbGUuY29tPoiQBBMWCAA4FiEE
+Ofs14EEfN47csfx2NngsF+2IresFAmrU6agCGwMFCwkIBwIGFQoJCAsCBBYCAwEC
+HgECF4AACgkQNngsF+2IrevYmwD/Z2FqkAZ7NrVbqsU73UbqkZLz0rsh5CeVLXvo
+dyyZ+p0A/3meXaDOUtC2pYx3FW/w9Bep8uE6SqHizkSpPeMYbPUL
+=Gtsb
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+// `gpg --enarmor` of "Hello, armor!\n" (GnuPG 2.2.40)
+const GPG_ENARMORED: &str = "-----BEGIN PGP ARMORED FILE-----
+Comment: Use \"gpg --dearmor\" for unpacking
+
+SGVsbG8sIGFybW9yIQo=
+=uZ7t
+-----END PGP ARMORED FILE-----
+";
+
+#[test]
+fn test_crc24() {
+    assert_eq!(crc24(b""), CRC24_INIT);
+    assert_eq!(crc24(b"Hello, armor!\n"), 0xB99EED);
+}
+
+#[test]
+fn test_gpg_message_roundtrip() {
+    let armor = decode(GPG_MESSAGE.as_bytes()).expect("decode should succeed");
+    assert_eq!(armor.label, "PGP MESSAGE");
+    assert!(armor.headers.is_empty());
+    // symmetric-key encrypted session key packet, version 4, AES256
+    assert_eq!(armor.data[..4], [0x8C, 0x0D, 0x04, 0x09]);
+    assert_eq!(armor.data.len(), 85);
+    assert_eq!(encode(&armor), GPG_MESSAGE.as_bytes());
+}
+
+#[test]
+fn test_gpg_public_key_roundtrip_preserves_headers() {
+    let armor = decode(GPG_PUBLIC_KEY.as_bytes()).expect("decode should succeed");
+    assert_eq!(armor.label, "PGP PUBLIC KEY BLOCK");
+    assert_eq!(
+        armor.headers,
+        [("Comment".to_string(), "Armor test key".to_string())]
+    );
+    // old format public key packet
+    assert_eq!(armor.data[0], 0x98);
+    assert_eq!(encode(&armor), GPG_PUBLIC_KEY.as_bytes());
+}
+
+#[test]
+fn test_gpg_enarmored_file() {
+    let armor = decode(GPG_ENARMORED.as_bytes()).expect("decode should succeed");
+    assert_eq!(armor.data, b"Hello, armor!\n");
+    assert_eq!(encode(&armor), GPG_ENARMORED.as_bytes());
+}
+
+#[test]
+fn test_checksum_is_optional() {
+    let armored = GPG_ENARMORED.replace("=uZ7t\n", "");
+    let armor = decode(armored.as_bytes()).expect("decode should succeed");
+    assert_eq!(armor.data, b"Hello, armor!\n");
+}
+
+#[test]
+fn test_checksum_mismatch() {
+    let armored = GPG_ENARMORED.replace("=uZ7t", "=uZ7u");
+    match decode(armored.as_bytes()) {
+        Err(ArmorError::ChecksumMismatch(0xB99EEE, 0xB99EED)) => {}
+        other => panic!(
+            "expected ChecksumMismatch, got {:?}",
+            other.map(|a| a.label)
+        ),
+    }
+    let armored = GPG_ENARMORED.replace("SGVsbG8s", "SGVsbG9s");
+    match decode(armored.as_bytes()) {
+        Err(ArmorError::ChecksumMismatch(0xB99EED, _)) => {}
+        other => panic!(
+            "expected ChecksumMismatch, got {:?}",
+            other.map(|a| a.label)
+        ),
+    }
+}
+
+#[test]
+fn test_decode_errors() {
+    match decode(b"no armor") {
+        Err(ArmorError::MissingBeginLine) => {}
+        other => panic!(
+            "expected MissingBeginLine, got {:?}",
+            other.map(|a| a.label)
+        ),
+    }
+    match decode(
+        GPG_ENARMORED
+            .replace("-----END PGP ARMORED FILE-----", "")
+            .as_bytes(),
+    ) {
+        Err(ArmorError::MissingEndLine(label)) if label == "PGP ARMORED FILE" => {}
+        other => panic!("expected MissingEndLine, got {:?}", other.map(|a| a.label)),
+    }
+    match decode(
+        GPG_ENARMORED
+            .replace("END PGP ARMORED", "END PGP")
+            .as_bytes(),
+    ) {
+        Err(ArmorError::MismatchedLabels(_, end)) if end == "PGP FILE" => {}
+        other => panic!(
+            "expected MismatchedLabels, got {:?}",
+            other.map(|a| a.label)
+        ),
+    }
+    match decode(
+        GPG_ENARMORED
+            .replace("Comment: Use", "Comment:Use")
+            .as_bytes(),
+    ) {
+        Err(ArmorError::InvalidHeader(_)) => {}
+        other => panic!("expected InvalidHeader, got {:?}", other.map(|a| a.label)),
+    }
+    match decode(GPG_ENARMORED.replace("=uZ7t", "=uZ7").as_bytes()) {
+        Err(ArmorError::InvalidChecksumLine(line)) if line == "=uZ7" => {}
+        other => panic!(
+            "expected InvalidChecksumLine, got {:?}",
+            other.map(|a| a.label)
+        ),
+    }
+    match decode(GPG_ENARMORED.replace("SGVs", "SG!s").as_bytes()) {
+        Err(ArmorError::InvalidBase64(Base64Error::InvalidCharacter(b'!'))) => {}
+        other => panic!("expected InvalidBase64, got {:?}", other.map(|a| a.label)),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", ArmorError::InvalidUtf8).contains("UTF-8"));
+    assert!(format!("{}", ArmorError::MissingBeginLine).contains("BEGIN"));
+    assert!(format!("{}", ArmorError::MissingEndLine("X".into())).contains("-----END X-----"));
+    assert!(format!("{}", ArmorError::MismatchedLabels("A".into(), "B".into())).contains("\"A\""));
+    assert!(format!("{}", ArmorError::InvalidHeader("x".into())).contains("header"));
+    let e = ArmorError::InvalidBase64(Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("Invalid character"));
+    assert!(format!("{}", ArmorError::InvalidChecksumLine("=x".into())).contains("=x"));
+    assert!(format!("{}", ArmorError::ChecksumMismatch(1, 2)).contains("000001"));
+}
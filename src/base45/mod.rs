@@ -0,0 +1,101 @@
+use std::error;
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+#[derive(Debug)]
+pub enum Base45Error {
+    InvalidCharacter(u8),
+    InvalidLength(usize),
+    ValueOutOfRange(u32),
+}
+
+impl error::Error for Base45Error {}
+
+impl std::fmt::Display for Base45Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base45Error::InvalidCharacter(c) => {
+                write!(f, "Invalid character \'{}\'", *c as char)
+            }
+            Base45Error::InvalidLength(length) => {
+                write!(f, "Length {} leaves a final group of one symbol", *length)
+            }
+            Base45Error::ValueOutOfRange(value) => {
+                write!(f, "Group value {} exceeds the range of its bytes", *value)
+            }
+        }
+    }
+}
+
+fn decode_symbol(b: u8) -> Result<u32, Base45Error> {
+    BASE45_ALPHABET
+        .iter()
+        .position(|c| *c == b)
+        .map(|digit| digit as u32)
+        .ok_or(Base45Error::InvalidCharacter(b))
+}
+
+fn unpack_pair(raw_pair: &[u8]) -> ([u8; 3], usize) {
+    let number = raw_pair.iter().fold(0u32, |n, b| (n << 8) | *b as u32);
+    let encoded_group = [
+        BASE45_ALPHABET[(number % 45) as usize],
+        BASE45_ALPHABET[(number / 45 % 45) as usize],
+        BASE45_ALPHABET[(number / (45 * 45)) as usize],
+    ];
+    (encoded_group, raw_pair.len() + 1)
+}
+
+fn pack_group(encoded_group: &[u8]) -> Result<([u8; 2], usize), Base45Error> {
+    let number = encoded_group
+        .iter()
+        .rev()
+        .try_fold(0u32, |n, b| Ok(n * 45 + decode_symbol(*b)?))?;
+    let num_bytes = encoded_group.len() - 1;
+    if number >= 1 << (8 * num_bytes) {
+        return Err(Base45Error::ValueOutOfRange(number));
+    }
+    match num_bytes {
+        2 => Ok(((number as u16).to_be_bytes(), 2)),
+        _ => Ok(([number as u8, 0], 1)),
+    }
+}
+
+fn validate_base45(base45_encoded_bytes: &[u8]) -> Result<(), Base45Error> {
+    let length = base45_encoded_bytes.len();
+    if length % 3 == 1 {
+        return Err(Base45Error::InvalidLength(length));
+    }
+    base45_encoded_bytes
+        .chunks(3)
+        .try_for_each(|group| pack_group(group).map(|_| ()))
+}
+
+pub fn decode(
+    base45_encoded_bytes: &[u8],
+) -> Result<impl Iterator<Item = u8> + use<'_>, Base45Error> {
+    validate_base45(base45_encoded_bytes)?;
+    Ok(base45_encoded_bytes
+        .chunks(3)
+        .map_while(|group| pack_group(group).ok())
+        .flat_map(|(bytes, length)| bytes.into_iter().take(length)))
+}
+
+pub fn decode_to_vec(base45_encoded_bytes: &[u8]) -> Result<Vec<u8>, Base45Error> {
+    let decoded_iter = decode(base45_encoded_bytes)?;
+    Ok(Vec::from_iter(decoded_iter))
+}
+
+pub fn encode(bytes: &[u8]) -> impl Iterator<Item = u8> + use<'_> {
+    bytes
+        .chunks(2)
+        .map(unpack_pair)
+        .flat_map(|(group, length)| group.into_iter().take(length))
+}
+
+pub fn encode_to_vec(bytes: &[u8]) -> Vec<u8> {
+    let encoded_iter = encode(bytes);
+    Vec::from_iter(encoded_iter)
+}
+
+#[cfg(test)]
+mod tests;
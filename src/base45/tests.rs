@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+fn test_rfc9285_encode_vectors() {
+    // RFC 9285 section 4.3
+    assert_eq!(encode_to_vec(b"AB"), b"BB8");
+    assert_eq!(encode_to_vec(b"Hello!!"), b"%69 VD92EX0");
+    assert_eq!(encode_to_vec(b"base-45"), b"UJCLQE7W581");
+}
+
+#[test]
+fn test_rfc9285_decode_vectors() {
+    // RFC 9285 section 4.4
+    let decoded = decode_to_vec(b"QED8WEX0").expect("decode should succeed");
+    assert_eq!(decoded, b"ietf!");
+    let decoded = decode_to_vec(b"%69 VD92EX0").expect("decode should succeed");
+    assert_eq!(decoded, b"Hello!!");
+}
+
+#[test]
+fn test_encode_decode_roundtrip() {
+    let input: Vec<u8> = (0..=255u8).collect();
+    for length in [0, 1, 2, 3, 255, 256] {
+        let encoded = encode_to_vec(&input[..length]);
+        assert_eq!(encoded.len(), length / 2 * 3 + length % 2 * 2);
+        let decoded = decode_to_vec(&encoded).expect("decode should succeed");
+        assert_eq!(decoded, &input[..length]);
+    }
+}
+
+#[test]
+fn test_largest_values() {
+    assert_eq!(encode_to_vec(&[0xFF, 0xFF]), b"FGW");
+    assert_eq!(decode_to_vec(b"FGW").expect("65535 decodes"), [0xFF, 0xFF]);
+    assert_eq!(encode_to_vec(&[0xFF]), b"U5");
+    assert_eq!(decode_to_vec(b"U5").expect("255 decodes"), [0xFF]);
+}
+
+#[test]
+fn test_triplet_value_exceeds_65535() {
+    // "GGW" = 16 + 16 * 45 + 32 * 45^2 = 65536
+    match decode_to_vec(b"GGW") {
+        Err(Base45Error::ValueOutOfRange(65536)) => {}
+        other => panic!("expected ValueOutOfRange(65536), got {:?}", other),
+    }
+    match decode_to_vec(b":::") {
+        Err(Base45Error::ValueOutOfRange(91124)) => {}
+        other => panic!("expected ValueOutOfRange(91124), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_final_pair_value_exceeds_255() {
+    // "V5" = 31 + 5 * 45 = 256
+    match decode_to_vec(b"V5") {
+        Err(Base45Error::ValueOutOfRange(256)) => {}
+        other => panic!("expected ValueOutOfRange(256), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_length_and_character() {
+    match decode_to_vec(b"BB8B") {
+        Err(Base45Error::InvalidLength(4)) => {}
+        other => panic!("expected InvalidLength(4), got {:?}", other),
+    }
+    match decode_to_vec(b"bb8") {
+        Err(Base45Error::InvalidCharacter(b'b')) => {}
+        other => panic!("expected InvalidCharacter('b'), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", Base45Error::InvalidCharacter(b'b')).contains("Invalid character"));
+    assert!(format!("{}", Base45Error::InvalidLength(4)).contains("Length 4"));
+    assert!(format!("{}", Base45Error::ValueOutOfRange(65536)).contains("65536"));
+}
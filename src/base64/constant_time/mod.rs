@@ -0,0 +1,142 @@
+pub use super::decoded_len;
+use super::{
+    Base64Config, Base64Error, Padding, pack_triplet, unpack_triplet, validate_length,
+    validate_padding,
+};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::hint::black_box;
+use core::sync::atomic::{Ordering, compiler_fence};
+
+/// `0xFF` if `a < b`, otherwise `0x00`, without comparing.
+fn lt_mask(a: u16, b: u16) -> u8 {
+    // the difference is negative exactly when a < b, and the arithmetic
+    // shift smears its sign bit over the whole word
+    black_box(((a as i32 - b as i32) >> 16) as u8)
+}
+
+fn ge_mask(a: u16, b: u16) -> u8 {
+    !lt_mask(a, b)
+}
+
+fn encode_symbol(config: &Base64Config, segment: u8) -> u8 {
+    let segment = segment as u16;
+    let mut symbol = 0u8;
+    let mut offset = 0u16;
+    for r in config.ranges() {
+        let start = *r.start() as u16;
+        let length = r.len() as u16;
+        let inside = ge_mask(segment, offset) & lt_mask(segment, offset + length);
+        symbol |= inside & start.wrapping_add(segment).wrapping_sub(offset) as u8;
+        offset += length;
+    }
+    symbol
+}
+
+/// Returns the 6-bit value of `symbol` and `0xFF` if it is in the alphabet,
+/// `0x00` otherwise.
+fn decode_symbol(config: &Base64Config, symbol: u8) -> (u8, u8) {
+    let symbol = symbol as u16;
+    let mut segment = 0u8;
+    let mut valid = 0u8;
+    let mut offset = 0u16;
+    for r in config.ranges() {
+        let start = *r.start() as u16;
+        let end = *r.end() as u16;
+        let inside = ge_mask(symbol, start) & lt_mask(symbol, end + 1);
+        segment |= inside & symbol.wrapping_sub(start).wrapping_add(offset) as u8;
+        valid |= inside;
+        offset += r.len() as u16;
+    }
+    (segment, valid)
+}
+
+/// Overwrites `bytes` with zeroes. The writes are volatile so they are not
+/// optimized away even though the memory is about to be freed.
+pub(super) fn wipe(bytes: &mut [u8]) {
+    for b in bytes.iter_mut() {
+        // SAFETY: `b` is a valid, aligned, exclusive reference
+        unsafe { core::ptr::write_volatile(b, 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Encodes into `output`, which needs room for
+/// [`Encoder::max_output_len`](super::Encoder::max_output_len) symbols, and
+/// returns the number written.
+pub fn encode_to_slice(config: &Base64Config, bytes: &[u8], output: &mut [u8]) -> usize {
+    let mut written = 0;
+    for chunk in bytes.chunks(3) {
+        let mut triplet = [0u8; 3];
+        triplet[..chunk.len()].copy_from_slice(chunk);
+        let mut segments = unpack_triplet(&triplet);
+        let num_segments = (chunk.len() * 8).div_ceil(6);
+        for segment in &segments[..num_segments] {
+            output[written] = encode_symbol(config, *segment);
+            written += 1;
+        }
+        wipe(&mut triplet);
+        wipe(&mut segments);
+    }
+    if let Padding::Required(c) | Padding::Optional(c) = config.padding {
+        let pad_length = (3 - bytes.len() % 3) % 3;
+        output[written..written + pad_length].fill(c);
+        written += pad_length;
+    }
+    written
+}
+
+#[cfg(feature = "alloc")]
+pub fn encode_to_vec(config: &Base64Config, bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = alloc::vec![0u8; super::Encoder::max_output_len(bytes.len())];
+    let length = encode_to_slice(config, bytes, &mut encoded);
+    encoded.truncate(length);
+    encoded
+}
+
+/// Decodes without secret-dependent branches into `output`, which needs
+/// room for [`decoded_len`] bytes, and returns the number written. Invalid
+/// characters are reported as [`Base64Error::InvalidSecretCharacter`], which
+/// deliberately does not say which character or where it was.
+pub fn decode_to_slice(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+    output: &mut [u8],
+) -> Result<usize, Base64Error> {
+    let trailing_pad_count = validate_padding(config, base64_encoded_bytes)?;
+    let unpadded_length = validate_length(config, base64_encoded_bytes.len(), trailing_pad_count)?;
+    let mut written = 0;
+    let mut valid = 0xFFu8;
+    for chunk in base64_encoded_bytes[..unpadded_length].chunks(4) {
+        let mut segments = [0u8; 4];
+        for (segment, symbol) in segments.iter_mut().zip(chunk) {
+            let (value, symbol_valid) = decode_symbol(config, *symbol);
+            *segment = value;
+            valid &= symbol_valid;
+        }
+        let mut triplet = pack_triplet(&segments);
+        let length = chunk.len() * 6 / 8;
+        output[written..written + length].copy_from_slice(&triplet[..length]);
+        written += length;
+        wipe(&mut segments);
+        wipe(&mut triplet);
+    }
+    if valid != 0xFF {
+        return Err(Base64Error::InvalidSecretCharacter);
+    }
+    Ok(written)
+}
+
+/// Like [`decode_to_slice`], allocating exactly the decoded length.
+#[cfg(feature = "alloc")]
+pub fn decode_to_vec(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+) -> Result<Vec<u8>, Base64Error> {
+    let mut decoded = alloc::vec![0u8; decoded_len(config, base64_encoded_bytes)?];
+    decode_to_slice(config, base64_encoded_bytes, &mut decoded)?;
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,170 @@
+use super::*;
+#[cfg(feature = "alloc")]
+use std::time::Instant;
+
+fn configs() -> [Base64Config; 4] {
+    [
+        Base64Config::standard(),
+        Base64Config::url(),
+        Base64Config::mime(),
+        Base64Config::new(
+            vec![
+                b'0'..=b'9',
+                b'A'..=b'Z',
+                b'a'..=b'z',
+                b'.'..=b'.',
+                b'/'..=b'/',
+            ],
+            Padding::NoPadding,
+        )
+        .expect("valid config"),
+    ]
+}
+
+#[cfg(feature = "alloc")]
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+#[test]
+fn test_symbol_mapping_matches_regular_codec() {
+    for config in configs() {
+        for b in 0..=255u8 {
+            let (segment, valid) = decode_symbol(&config, b);
+            match super::super::decode_symbol(&config, b) {
+                Ok(expected) => assert_eq!((segment, valid), (expected, 0xFF)),
+                Err(_) => assert_eq!(valid, 0x00),
+            }
+        }
+        for segment in 0..64u8 {
+            assert_eq!(
+                encode_symbol(&config, segment),
+                super::super::encode_byte(&config, segment)
+            );
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_matches_regular_codec() {
+    let mut state = 0x2545_f491_4f6c_dd1d;
+    let bytes: Vec<u8> = (0..40).map(|_| xorshift(&mut state) as u8).collect();
+    for config in configs() {
+        for length in 0..bytes.len() {
+            let encoded = encode_to_vec(&config, &bytes[..length]);
+            assert_eq!(
+                encoded,
+                super::super::encode_to_vec(&config, &bytes[..length])
+            );
+            let decoded = decode_to_vec(&config, &encoded).expect("decode should succeed");
+            assert_eq!(decoded, &bytes[..length]);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_errors() {
+    let config = Base64Config::standard();
+    match decode_to_vec(&config, b"SGVsbG8sIS!") {
+        Err(Base64Error::InvalidSecretCharacter) => {}
+        other => panic!("expected InvalidSecretCharacter, got {:?}", other),
+    }
+    // '=' is only padding at the end
+    match decode_to_vec(&config, b"AA=A") {
+        Err(Base64Error::InvalidSecretCharacter) => {}
+        other => panic!("expected InvalidSecretCharacter, got {:?}", other),
+    }
+    match decode_to_vec(&config, b"AAAA===") {
+        Err(Base64Error::TooManyPaddingCharacters(3)) => {}
+        other => panic!("expected TooManyPaddingCharacters(3), got {:?}", other),
+    }
+    match decode_to_vec(&Base64Config::mime(), b"AA") {
+        Err(Base64Error::InvalidLength(2, b'=')) => {}
+        other => panic!("expected InvalidLength, got {:?}", other),
+    }
+    assert!(format!("{}", Base64Error::InvalidSecretCharacter).contains("not shown"));
+}
+
+#[cfg(feature = "alloc")]
+fn welch_t(a: &[f64], b: &[f64]) -> f64 {
+    let mean_variance = |xs: &[f64]| {
+        let mean = xs.iter().sum::<f64>() / xs.len() as f64;
+        let variance = xs.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (xs.len() - 1) as f64;
+        (mean, variance)
+    };
+    let (mean_a, variance_a) = mean_variance(a);
+    let (mean_b, variance_b) = mean_variance(b);
+    (mean_a - mean_b) / (variance_a / a.len() as f64 + variance_b / b.len() as f64).sqrt()
+}
+
+/// dudect-style leakage test: times `f` on two classes of inputs in a
+/// random interleaving, drops the slowest 10% of measurements (interrupts,
+/// preemption) and returns Welch's t statistic between the classes.
+#[cfg(feature = "alloc")]
+fn leakage_t(inputs: [&[u8]; 2], f: impl Fn(&[u8])) -> f64 {
+    const SAMPLES: usize = 6000;
+    let mut state = 0x9e37_79b9_7f4a_7c15;
+    let mut timings = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        let class = (xorshift(&mut state) & 1) as usize;
+        let input = std::hint::black_box(inputs[class]);
+        let start = Instant::now();
+        f(input);
+        timings.push((class, start.elapsed().as_nanos() as f64));
+    }
+    let mut sorted: Vec<f64> = timings.iter().map(|(_, t)| *t).collect();
+    sorted.sort_by(f64::total_cmp);
+    let cutoff = sorted[sorted.len() * 9 / 10];
+    let class_timings = |class| {
+        timings
+            .iter()
+            .filter(|(c, t)| *c == class && *t <= cutoff)
+            .map(|(_, t)| *t)
+            .collect::<Vec<f64>>()
+    };
+    welch_t(&class_timings(0), &class_timings(1))
+}
+
+// |t| above 10 is dudect's threshold for a definite leak
+#[cfg(feature = "alloc")]
+const LEAKAGE_THRESHOLD: f64 = 10.0;
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_timing_does_not_depend_on_input() {
+    let config = Base64Config::standard();
+    let mut state = 0x853c_49e6_748f_ea9b;
+    let secret: Vec<u8> = (0..384).map(|_| xorshift(&mut state) as u8).collect();
+    let random = encode_to_vec(&config, &secret);
+    let fixed = vec![b'A'; random.len()];
+    let mut bad_first = random.clone();
+    bad_first[0] = b'!';
+    let mut bad_last = random.clone();
+    *bad_last.last_mut().unwrap() = b'!';
+
+    let decode_ct = |input: &[u8]| {
+        let _ = std::hint::black_box(decode_to_vec(&config, input));
+    };
+    let t = leakage_t([&fixed, &random], decode_ct);
+    assert!(
+        t.abs() < LEAKAGE_THRESHOLD,
+        "fixed vs random symbols: t = {t}"
+    );
+    let t = leakage_t([&bad_first, &bad_last], decode_ct);
+    assert!(
+        t.abs() < LEAKAGE_THRESHOLD,
+        "invalid symbol position: t = {t}"
+    );
+
+    // the same measurement does pick up the early exit of the regular decoder
+    let decode_regular = |input: &[u8]| {
+        let _ = std::hint::black_box(super::super::decode_to_vec(&config, input));
+    };
+    let t = leakage_t([&bad_first, &bad_last], decode_regular);
+    assert!(t.abs() > LEAKAGE_THRESHOLD, "regular decoder: t = {t}");
+}
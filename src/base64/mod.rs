@@ -1,7 +1,22 @@
-use std::error;
-use std::ops::RangeInclusive;
+/// Encoding and decoding without data-dependent branches or early exits,
+/// for private keys and tokens
+pub mod constant_time;
+/// Encoding and decoding of large inputs split across worker threads
+#[cfg(feature = "std")]
+pub mod parallel;
+/// Decoded secrets that are wiped from memory when dropped
+#[cfg(feature = "alloc")]
+pub mod secret;
 
-#[allow(dead_code)]
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::error;
+use core::ops::RangeInclusive;
+
+/// An alphabet of 64 symbols has at most 64 non-empty ranges
+const MAX_RANGES: usize = 64;
+
+#[allow(clippy::enum_variant_names)]
 pub enum Padding {
     Required(u8),
     Optional(u8),
@@ -9,7 +24,8 @@ pub enum Padding {
 }
 
 pub struct Base64Config {
-    ranges: Vec<RangeInclusive<u8>>,
+    ranges: [RangeInclusive<u8>; MAX_RANGES],
+    range_count: usize,
     padding: Padding,
 }
 
@@ -26,13 +42,14 @@ pub enum Base64Error {
     InvalidLength(usize, u8),
     HasPaddingAndLengthNotMultipleOf4(usize),
     TooManyPaddingCharacters(usize),
+    InvalidSecretCharacter,
 }
 
 impl error::Error for Base64ConfigError {}
 impl error::Error for Base64Error {}
 
-impl std::fmt::Display for Base64ConfigError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Base64ConfigError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Base64ConfigError::OverlappingRanges(range1, range2) => {
                 write!(
@@ -60,8 +77,8 @@ impl std::fmt::Display for Base64ConfigError {
     }
 }
 
-impl std::fmt::Display for Base64Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for Base64Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Base64Error::InvalidCharacter(c) => {
                 write!(f, "Invalid character \'{}\'", *c as char)
@@ -83,24 +100,98 @@ impl std::fmt::Display for Base64Error {
             Base64Error::TooManyPaddingCharacters(count) => {
                 write!(f, "Too many padding characters: {}", *count)
             }
+            Base64Error::InvalidSecretCharacter => {
+                write!(f, "Invalid character (not shown, the input is secret)")
+            }
         }
     }
 }
 
 impl Base64Config {
-    #[allow(dead_code)]
     pub fn new(
-        ranges: Vec<RangeInclusive<u8>>,
+        ranges: impl IntoIterator<Item = RangeInclusive<u8>>,
         padding: Padding,
     ) -> Result<Self, Base64ConfigError> {
-        let config = Self { ranges, padding };
+        let config = Self::unvalidated(ranges, padding)?;
         validate_config(&config)?;
         Ok(config)
     }
-    #[allow(dead_code)]
-    pub fn standard() -> Self {
-        Base64Config::new(
-            vec![
+
+    /// Stores the non-empty ranges inline, without checking them against
+    /// each other or the padding.
+    fn unvalidated(
+        ranges: impl IntoIterator<Item = RangeInclusive<u8>>,
+        padding: Padding,
+    ) -> Result<Self, Base64ConfigError> {
+        let mut config = Self {
+            ranges: [const { 0..=0 }; MAX_RANGES],
+            range_count: 0,
+            padding,
+        };
+        let mut len_sum = 0;
+        for r in ranges.into_iter().filter(|r| !r.is_empty()) {
+            len_sum += r.len();
+            if config.range_count == MAX_RANGES {
+                // a 65th non-empty range pushes the sum past 64 anyway
+                continue;
+            }
+            config.ranges[config.range_count] = r;
+            config.range_count += 1;
+        }
+        if len_sum > 64 {
+            return Err(Base64ConfigError::RangeLengthsDoNotSumTo64(len_sum));
+        }
+        Ok(config)
+    }
+
+    /// Like [`Base64Config::new`], but usable in const items.
+    pub const fn from_ranges(
+        ranges: &[RangeInclusive<u8>],
+        padding: Padding,
+    ) -> Result<Self, Base64ConfigError> {
+        let mut config = Self {
+            ranges: [const { 0..=0 }; MAX_RANGES],
+            range_count: 0,
+            padding,
+        };
+        let mut len_sum = 0;
+        let mut i = 0;
+        while i < ranges.len() {
+            let (start, end) = (*ranges[i].start(), *ranges[i].end());
+            i += 1;
+            if start > end {
+                continue;
+            }
+            len_sum += (end - start) as usize + 1;
+            if config.range_count < MAX_RANGES {
+                config.ranges[config.range_count] = start..=end;
+                config.range_count += 1;
+            }
+        }
+        if len_sum > 64 {
+            return Err(Base64ConfigError::RangeLengthsDoNotSumTo64(len_sum));
+        }
+        match validate_config(&config) {
+            Ok(()) => Ok(config),
+            Err(e) => Err(e),
+        }
+    }
+
+    const fn ranges(&self) -> &[RangeInclusive<u8>] {
+        self.ranges.split_at(self.range_count).0
+    }
+
+    /// Unwraps a preset, which is known to be valid.
+    const fn preset(config: Result<Self, Base64ConfigError>) -> Self {
+        match config {
+            Ok(config) => config,
+            Err(_) => panic!("invalid preset alphabet"),
+        }
+    }
+
+    pub const fn standard() -> Self {
+        Self::preset(Base64Config::from_ranges(
+            &[
                 b'A'..=b'Z',
                 b'a'..=b'z',
                 b'0'..=b'9',
@@ -108,13 +199,11 @@ impl Base64Config {
                 b'/'..=b'/',
             ],
             Padding::Optional(b'='),
-        )
-        .unwrap()
+        ))
     }
-    #[allow(dead_code)]
-    pub fn url() -> Self {
-        Base64Config::new(
-            vec![
+    pub const fn url() -> Self {
+        Self::preset(Base64Config::from_ranges(
+            &[
                 b'A'..=b'Z',
                 b'a'..=b'z',
                 b'0'..=b'9',
@@ -122,13 +211,11 @@ impl Base64Config {
                 b'_'..=b'_',
             ],
             Padding::Optional(b'='),
-        )
-        .unwrap()
+        ))
     }
-    #[allow(dead_code)]
-    pub fn mime() -> Self {
-        Base64Config::new(
-            vec![
+    pub const fn mime() -> Self {
+        Self::preset(Base64Config::from_ranges(
+            &[
                 b'A'..=b'Z',
                 b'a'..=b'z',
                 b'0'..=b'9',
@@ -136,81 +223,85 @@ impl Base64Config {
                 b'/'..=b'/',
             ],
             Padding::Required(b'='),
-        )
-        .unwrap()
-    }
-}
-
-fn choose2<'a, Type: 'a>(
-    iter: impl Iterator<Item = &'a Type> + Clone,
-) -> impl Iterator<Item = (&'a Type, &'a Type)> {
-    let mut n: usize = 1;
-    let mut outer = iter.clone().peekable();
-    let mut inner = iter.clone().skip(1);
-    std::iter::from_fn(move || {
-        let outer_el;
-        if let Some(inner_el) = inner.next() {
-            outer_el = *outer.peek()?;
-            return Some((outer_el, inner_el));
-        } else {
-            _ = outer.next()?;
-            outer_el = *outer.peek()?;
-            inner = iter.clone().skip({
-                n += 1;
-                n
-            });
-            let inner_el = inner.next()?;
-            return Some((outer_el, inner_el));
-        }
-    })
+        ))
+    }
+    /// The same alphabet with different padding rules, e.g. an unpadded `url()`.
+    pub const fn with_padding(self, padding: Padding) -> Result<Self, Base64ConfigError> {
+        Base64Config::from_ranges(self.ranges(), padding)
+    }
 }
 
-fn ranges_overlap<T: std::cmp::PartialOrd>(r1: &RangeInclusive<T>, r2: &RangeInclusive<T>) -> bool {
-    !((r1.end() < r2.start()) || (r1.start() > r2.end()))
+/// The ranges stored in a config are never empty, so this is their length.
+const fn range_len(r: &RangeInclusive<u8>) -> usize {
+    (*r.end() - *r.start()) as usize + 1
 }
 
-fn validate_config(config: &Base64Config) -> Result<(), Base64ConfigError> {
-    if let Some((r1, r2)) = choose2(config.ranges.iter())
-        .filter(|(r1, r2)| ranges_overlap(r1, r2))
-        .next()
-    {
-        return Err(Base64ConfigError::OverlappingRanges(r1.clone(), r2.clone()));
+const fn range_contains(r: &RangeInclusive<u8>, b: u8) -> bool {
+    *r.start() <= b && b <= *r.end()
+}
+
+// Loops are spelled out with `while` so that configs can be checked in
+// const items.
+const fn validate_config(config: &Base64Config) -> Result<(), Base64ConfigError> {
+    let ranges = config.ranges();
+    let mut i = 0;
+    while i < ranges.len() {
+        let mut j = i + 1;
+        while j < ranges.len() {
+            let (r1, r2) = (&ranges[i], &ranges[j]);
+            if !(*r1.end() < *r2.start() || *r1.start() > *r2.end()) {
+                return Err(Base64ConfigError::OverlappingRanges(
+                    *r1.start()..=*r1.end(),
+                    *r2.start()..=*r2.end(),
+                ));
+            }
+            j += 1;
+        }
+        i += 1;
     }
     if let Padding::Required(c) | Padding::Optional(c) = config.padding {
-        for r in &config.ranges {
-            if r.contains(&c) {
-                return Err(Base64ConfigError::PaddingCharInRange(c, r.clone()));
+        let mut i = 0;
+        while i < ranges.len() {
+            if range_contains(&ranges[i], c) {
+                return Err(Base64ConfigError::PaddingCharInRange(
+                    c,
+                    *ranges[i].start()..=*ranges[i].end(),
+                ));
             }
+            i += 1;
         }
     }
-    let len_sum = config.ranges.iter().map(|r| r.len()).sum::<usize>();
+    let mut len_sum = 0;
+    let mut i = 0;
+    while i < ranges.len() {
+        len_sum += range_len(&ranges[i]);
+        i += 1;
+    }
     if len_sum != 64usize {
         return Err(Base64ConfigError::RangeLengthsDoNotSumTo64(len_sum));
     }
-    return Ok(());
+    Ok(())
 }
 
-fn count_trailing_pad_characters(config: &Base64Config, base64_encoded_bytes: &[u8]) -> usize {
-    fn count_from_back(bytes: &[u8], p: u8) -> usize {
-        match bytes
-            .iter()
-            .rev()
-            .enumerate()
-            .map_while(|(i, c)| if i <= 2 && *c == p { Some(i) } else { None })
-            .last()
+const fn count_trailing_pad_characters(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+) -> usize {
+    if let Padding::Required(p) | Padding::Optional(p) = config.padding {
+        let mut count = 0;
+        while count <= 2
+            && count < base64_encoded_bytes.len()
+            && base64_encoded_bytes[base64_encoded_bytes.len() - 1 - count] == p
         {
-            Some(i) => i + 1,
-            None => 0,
+            count += 1;
         }
-    }
-    if let Padding::Required(p) | Padding::Optional(p) = config.padding {
-        return count_from_back(base64_encoded_bytes, p);
+        count
     } else {
-        return 0;
+        0
     }
 }
 
-fn validate_base64(
+const fn validate_padding(
     config: &Base64Config,
     base64_encoded_bytes: &[u8],
 ) -> Result<usize, Base64Error> {
@@ -218,143 +309,540 @@ fn validate_base64(
     if trailing_pad_count >= 3 {
         return Err(Base64Error::TooManyPaddingCharacters(trailing_pad_count));
     }
-    for c in base64_encoded_bytes.iter().rev().skip(trailing_pad_count) {
-        if !config.ranges.iter().any(|r| r.contains(&c)) {
-            return Err(Base64Error::InvalidCharacter(*c));
-        }
-    }
-    let length = base64_encoded_bytes.len();
-    if let Padding::Required(c) = config.padding {
-        if length % 4 != 0 {
-            return Err(Base64Error::InvalidLength(length, c));
-        }
+    Ok(trailing_pad_count)
+}
+
+/// Checks the length against the padding rules and returns the unpadded
+/// length. Only looks at the length, never at the symbols themselves.
+const fn validate_length(
+    config: &Base64Config,
+    length: usize,
+    trailing_pad_count: usize,
+) -> Result<usize, Base64Error> {
+    if let Padding::Required(c) = config.padding
+        && !length.is_multiple_of(4)
+    {
+        return Err(Base64Error::InvalidLength(length, c));
     }
-    if let Padding::Optional(_) = config.padding {
-        if trailing_pad_count != 0 && length % 4 != 0 {
-            return Err(Base64Error::HasPaddingAndLengthNotMultipleOf4(length));
-        }
+    if let Padding::Optional(_) = config.padding
+        && trailing_pad_count != 0
+        && !length.is_multiple_of(4)
+    {
+        return Err(Base64Error::HasPaddingAndLengthNotMultipleOf4(length));
     }
     Ok(length - trailing_pad_count)
 }
 
-fn decode_byte(config: &Base64Config, b: u8) -> u8 {
+const fn decode_byte(config: &Base64Config, b: u8) -> u8 {
+    let ranges = config.ranges();
     let mut offset = 0;
-    for r in &config.ranges {
-        if r.contains(&b) {
-            return b - r.start() + offset;
+    let mut i = 0;
+    while i < ranges.len() {
+        if range_contains(&ranges[i], b) {
+            return b - *ranges[i].start() + offset;
         }
-        offset += r.len() as u8;
+        offset += range_len(&ranges[i]) as u8;
+        i += 1;
     }
-    return 0u8;
+    0u8
 }
 
-fn encode_byte(config: &Base64Config, b: u8) -> u8 {
+/// Maps a symbol to its 6-bit value.
+pub(crate) const fn decode_symbol(config: &Base64Config, b: u8) -> Result<u8, Base64Error> {
+    let ranges = config.ranges();
+    let mut i = 0;
+    while i < ranges.len() && !range_contains(&ranges[i], b) {
+        i += 1;
+    }
+    if i == ranges.len() {
+        return Err(Base64Error::InvalidCharacter(b));
+    }
+    Ok(decode_byte(config, b))
+}
+
+/// Maps a 6-bit value to its symbol (0 for values above 63).
+pub(crate) const fn encode_byte(config: &Base64Config, b: u8) -> u8 {
+    let ranges = config.ranges();
     let mut b_minus_offset = b;
-    for r in &config.ranges {
-        if b_minus_offset < r.len() as u8 {
-            return r.start() + b_minus_offset;
+    let mut i = 0;
+    while i < ranges.len() {
+        if b_minus_offset < range_len(&ranges[i]) as u8 {
+            return *ranges[i].start() + b_minus_offset;
         }
-        b_minus_offset -= r.len() as u8;
+        b_minus_offset -= range_len(&ranges[i]) as u8;
+        i += 1;
     }
-    return 0u8;
+    0u8
 }
 
-fn unpack_triplet(raw_triplet: &[u8]) -> [u8; 4] {
+const fn unpack_triplet(raw_triplet: &[u8]) -> [u8; 4] {
     let bits_per_element: usize = 6;
     let bits_per_byte: usize = 8;
-    let number = (raw_triplet[0] as u32) << bits_per_byte * 2
-        | (raw_triplet[1] as u32) << bits_per_byte * 1
+    let number = (raw_triplet[0] as u32) << (bits_per_byte * 2)
+        | (raw_triplet[1] as u32) << bits_per_byte
         | (raw_triplet[2] as u32);
     let element_mask = (1u32 << bits_per_element) - 1;
     [
-        ((number >> bits_per_element * 3) & element_mask) as u8,
-        ((number >> bits_per_element * 2) & element_mask) as u8,
-        ((number >> bits_per_element * 1) & element_mask) as u8,
+        ((number >> (bits_per_element * 3)) & element_mask) as u8,
+        ((number >> (bits_per_element * 2)) & element_mask) as u8,
+        ((number >> bits_per_element) & element_mask) as u8,
         (number & element_mask) as u8,
     ]
 }
 
-fn pack_triplet(encoded_triplet: &[u8]) -> [u8; 3] {
+const fn pack_triplet(encoded_triplet: &[u8]) -> [u8; 3] {
     let bits_per_element: usize = 6;
     let bits_per_byte: usize = 8;
-    let number = (encoded_triplet[0] as u32) << bits_per_element * 3
-        | (encoded_triplet[1] as u32) << bits_per_element * 2
-        | (encoded_triplet[2] as u32) << bits_per_element * 1
+    let number = (encoded_triplet[0] as u32) << (bits_per_element * 3)
+        | (encoded_triplet[1] as u32) << (bits_per_element * 2)
+        | (encoded_triplet[2] as u32) << bits_per_element
         | (encoded_triplet[3] as u32);
     let byte_mask = (1u32 << bits_per_byte) - 1;
     [
-        ((number >> bits_per_byte * 2) & byte_mask) as u8,
-        ((number >> bits_per_byte * 1) & byte_mask) as u8,
+        ((number >> (bits_per_byte * 2)) & byte_mask) as u8,
+        ((number >> bits_per_byte) & byte_mask) as u8,
         (number & byte_mask) as u8,
     ]
 }
 
-fn chunk_iter<T: Default + Copy, const CHUNK_SIZE: usize, U: Iterator<Item = T> + Clone>(
-    iter: &U,
-) -> impl Iterator<Item = [T; CHUNK_SIZE]> + use<T, CHUNK_SIZE, U> {
-    let mut iter = iter.clone();
-    std::iter::from_fn(move || {
-        let mut array_chunk: [T; CHUNK_SIZE] = [T::default(); CHUNK_SIZE];
-        for i in 0..CHUNK_SIZE {
-            array_chunk[i] = iter.next()?;
+/// Incremental encoder: feed it input in pieces of any size with
+/// [`Encoder::update`] and collect the last symbols and padding with
+/// [`Encoder::finish`]. Holds up to two bytes of a partial triplet between calls.
+pub struct Encoder<'a> {
+    config: &'a Base64Config,
+    triplet: [u8; 3],
+    triplet_len: usize,
+}
+
+impl<'a> Encoder<'a> {
+    pub const fn new(config: &'a Base64Config) -> Self {
+        Encoder {
+            config,
+            triplet: [0u8; 3],
+            triplet_len: 0,
         }
-        Some(array_chunk)
-    })
+    }
+
+    /// Room `update` may need in `output` for `input_len` more bytes of input.
+    pub const fn max_output_len(input_len: usize) -> usize {
+        input_len.div_ceil(3) * 4
+    }
+
+    /// Encodes every complete triplet available after appending `input` and
+    /// returns the number of symbols written to `output`.
+    ///
+    /// Panics if `output` is shorter than [`Encoder::max_output_len`].
+    pub const fn update(&mut self, input: &[u8], output: &mut [u8]) -> usize {
+        let mut written = 0;
+        let mut i = 0;
+        while i < input.len() {
+            self.triplet[self.triplet_len] = input[i];
+            self.triplet_len += 1;
+            if self.triplet_len == 3 {
+                let segments = unpack_triplet(&self.triplet);
+                let mut j = 0;
+                while j < 4 {
+                    output[written] = encode_byte(self.config, segments[j]);
+                    written += 1;
+                    j += 1;
+                }
+                self.triplet_len = 0;
+            }
+            i += 1;
+        }
+        written
+    }
+
+    /// Encodes the partial triplet left over, followed by any padding the
+    /// config calls for. Returns the symbols and how many of them are used.
+    pub const fn finish(self) -> ([u8; 4], usize) {
+        let mut symbols = [0u8; 4];
+        if self.triplet_len == 0 {
+            return (symbols, 0);
+        }
+        let mut triplet = [0u8; 3];
+        let mut i = 0;
+        while i < self.triplet_len {
+            triplet[i] = self.triplet[i];
+            i += 1;
+        }
+        let bits_per_segment = 6usize;
+        let bits_per_byte = 8usize;
+        let num_segments = (self.triplet_len * bits_per_byte).div_ceil(bits_per_segment);
+        let segments = unpack_triplet(&triplet);
+        let mut i = 0;
+        while i < 4 {
+            symbols[i] = encode_byte(self.config, segments[i]);
+            i += 1;
+        }
+        match self.config.padding {
+            Padding::Required(c) | Padding::Optional(c) => {
+                let mut i = num_segments;
+                while i < 4 {
+                    symbols[i] = c;
+                    i += 1;
+                }
+                (symbols, 4)
+            }
+            Padding::NoPadding => (symbols, num_segments),
+        }
+    }
+}
+
+/// Incremental decoder, the counterpart of [`Encoder`]. Holds up to three
+/// symbols of a partial quad, and the padding seen so far, between calls.
+pub struct Decoder<'a> {
+    config: &'a Base64Config,
+    quad: [u8; 4],
+    quad_len: usize,
+    length: usize,
+    trailing_pad_count: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub const fn new(config: &'a Base64Config) -> Self {
+        Decoder {
+            config,
+            quad: [0u8; 4],
+            quad_len: 0,
+            length: 0,
+            trailing_pad_count: 0,
+        }
+    }
+
+    /// Room `update` may need in `output` for `input_len` more symbols of input.
+    pub const fn max_output_len(input_len: usize) -> usize {
+        input_len / 4 * 3 + 3
+    }
+
+    /// Decodes every complete quad available after appending `input` and
+    /// returns the number of bytes written to `output`. Padding is only
+    /// accepted at the very end, so a symbol after it is an error.
+    ///
+    /// Panics if `output` is shorter than [`Decoder::max_output_len`].
+    pub const fn update(&mut self, input: &[u8], output: &mut [u8]) -> Result<usize, Base64Error> {
+        let mut written = 0;
+        let mut i = 0;
+        while i < input.len() {
+            let c = input[i];
+            i += 1;
+            self.length += 1;
+            if let Padding::Required(p) | Padding::Optional(p) = self.config.padding {
+                if c == p {
+                    self.trailing_pad_count += 1;
+                    if self.trailing_pad_count >= 3 {
+                        return Err(Base64Error::TooManyPaddingCharacters(
+                            self.trailing_pad_count,
+                        ));
+                    }
+                    continue;
+                }
+                if self.trailing_pad_count > 0 {
+                    return Err(Base64Error::InvalidCharacter(c));
+                }
+            }
+            self.quad[self.quad_len] = match decode_symbol(self.config, c) {
+                Ok(segment) => segment,
+                Err(e) => return Err(e),
+            };
+            self.quad_len += 1;
+            if self.quad_len == 4 {
+                let triplet = pack_triplet(&self.quad);
+                output[written] = triplet[0];
+                output[written + 1] = triplet[1];
+                output[written + 2] = triplet[2];
+                written += 3;
+                self.quad_len = 0;
+            }
+        }
+        Ok(written)
+    }
+
+    /// Checks the total length against the padding rules and decodes the
+    /// partial quad left over. Returns the bytes and how many of them are used.
+    pub const fn finish(self) -> Result<([u8; 3], usize), Base64Error> {
+        if let Err(e) = validate_length(self.config, self.length, self.trailing_pad_count) {
+            return Err(e);
+        }
+        let mut quad = [0u8; 4];
+        let mut i = 0;
+        while i < self.quad_len {
+            quad[i] = self.quad[i];
+            i += 1;
+        }
+        let bits_per_segment = 6usize;
+        let bits_per_byte = 8usize;
+        let num_bytes = self.quad_len * bits_per_segment / bits_per_byte;
+        Ok((pack_triplet(&quad), num_bytes))
+    }
+}
+
+/// Decodes into `output`, which needs room for
+/// [`Decoder::max_output_len`] bytes, and returns the number written.
+pub fn decode_to_slice(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+    output: &mut [u8],
+) -> Result<usize, Base64Error> {
+    let mut decoder = Decoder::new(config);
+    let length = decoder.update(base64_encoded_bytes, output)?;
+    let (tail, tail_length) = decoder.finish()?;
+    output[length..length + tail_length].copy_from_slice(&tail[..tail_length]);
+    Ok(length + tail_length)
 }
 
 pub fn decode<'a>(
     config: &'a Base64Config,
     base64_encoded_bytes: &'a [u8],
 ) -> Result<impl Iterator<Item = u8> + use<'a>, Base64Error> {
-    let unpadded_length = validate_base64(config, &base64_encoded_bytes)?;
-    let pad_length = (4 - (unpadded_length % 4)) % 4;
-    let zeroes = [0u8].repeat(pad_length);
-    let padded_base64_encoded_bytes = base64_encoded_bytes.iter().map(|c| *c).chain(zeroes);
-    let padded_segments = padded_base64_encoded_bytes.map(|b| decode_byte(config, b));
-    let chunked_segments = chunk_iter::<u8, 4, _>(&padded_segments);
-    let chunked_bytes = chunked_segments.map(|chunk| pack_triplet(chunk.as_slice()));
-    let decoded_bytes = chunked_bytes.flatten();
-    let bits_per_segment = 6usize;
-    let bits_per_byte = 8usize;
-    let num_bytes_unpadded = (unpadded_length * bits_per_segment) / bits_per_byte;
-    Ok(decoded_bytes.take(num_bytes_unpadded))
+    // a first pass validates, so the iterator itself cannot fail
+    let mut decoder = Decoder::new(config);
+    let mut scratch = [0u8; 6];
+    for quad in base64_encoded_bytes.chunks(4) {
+        decoder.update(quad, &mut scratch)?;
+    }
+    decoder.finish()?;
+
+    let mut decoder = Some(Decoder::new(config));
+    let mut quads = base64_encoded_bytes.chunks(4);
+    Ok(core::iter::from_fn(move || {
+        let mut bytes = [0u8; 6];
+        match quads.next() {
+            Some(quad) => {
+                let length = decoder.as_mut()?.update(quad, &mut bytes).ok()?;
+                Some((bytes, length))
+            }
+            None => {
+                let (tail, length) = decoder.take()?.finish().ok()?;
+                bytes[..3].copy_from_slice(&tail);
+                Some((bytes, length))
+            }
+        }
+    })
+    .flat_map(|(bytes, length)| bytes.into_iter().take(length)))
 }
 
+#[cfg(feature = "alloc")]
 pub fn decode_to_vec(
     config: &Base64Config,
     base64_encoded_bytes: &[u8],
 ) -> Result<Vec<u8>, Base64Error> {
-    let decoded_iter = decode(config, base64_encoded_bytes)?;
-    Ok(Vec::from_iter(decoded_iter))
-}
-
-pub fn encode(config: &Base64Config, bytes: &[u8]) -> impl Iterator<Item = u8> {
-    let pad_length = (3 - bytes.len() % 3) % 3;
-    let zeroes = [0u8].repeat(pad_length);
-    let padded_bytes = bytes.iter().map(|c| *c).chain(zeroes);
-    let chunked_bytes = chunk_iter::<u8, 3, _>(&padded_bytes);
-    let chunked_segments = chunked_bytes.map(|chunk| unpack_triplet(chunk.as_slice()));
-    let segments = chunked_segments.flatten();
-    let base64_encoded_segments = segments.map(|b| encode_byte(config, b));
-    let bits_per_segment = 6usize;
-    let bits_per_byte = 8usize;
-    let num_segments_unpadded =
-        (bytes.len() * bits_per_byte + bits_per_segment - 1) / bits_per_segment;
-    let num_pad_segments = pad_length * bits_per_byte / bits_per_segment;
+    let mut decoded = alloc::vec![0u8; Decoder::max_output_len(base64_encoded_bytes.len())];
+    let length = decode_to_slice(config, base64_encoded_bytes, &mut decoded)?;
+    decoded.truncate(length);
+    Ok(decoded)
+}
+
+/// Encodes into `output`, which needs room for [`Encoder::max_output_len`]
+/// symbols, and returns the number written.
+pub fn encode_to_slice(config: &Base64Config, bytes: &[u8], output: &mut [u8]) -> usize {
+    let mut encoder = Encoder::new(config);
+    let length = encoder.update(bytes, output);
+    let (tail, tail_length) = encoder.finish();
+    output[length..length + tail_length].copy_from_slice(&tail[..tail_length]);
+    length + tail_length
+}
+
+pub fn encode<'a>(config: &'a Base64Config, bytes: &'a [u8]) -> impl Iterator<Item = u8> + use<'a> {
+    let mut encoder = Some(Encoder::new(config));
+    let mut triplets = bytes.chunks(3);
+    core::iter::from_fn(move || {
+        let mut symbols = [0u8; 4];
+        match triplets.next() {
+            Some(triplet) => {
+                let length = encoder.as_mut()?.update(triplet, &mut symbols);
+                Some((symbols, length))
+            }
+            None => encoder.take().map(Encoder::finish),
+        }
+    })
+    .flat_map(|(symbols, length)| symbols.into_iter().take(length))
+}
+
+#[cfg(feature = "alloc")]
+pub fn encode_to_vec(config: &Base64Config, bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = alloc::vec![0u8; Encoder::max_output_len(bytes.len())];
+    let length = encode_to_slice(config, bytes, &mut encoded);
+    encoded.truncate(length);
+    encoded
+}
+
+/// Returns the number of symbols `input_len` bytes encode to.
+pub const fn encoded_len(config: &Base64Config, input_len: usize) -> usize {
     match config.padding {
-        Padding::Required(c) | Padding::Optional(c) => base64_encoded_segments
-            .take(num_segments_unpadded)
-            .chain([c].repeat(num_pad_segments)),
-        Padding::NoPadding => base64_encoded_segments
-            .take(num_segments_unpadded)
-            .chain([0u8].repeat(0)), // to obtain the same type
+        Padding::Required(_) | Padding::Optional(_) => input_len.div_ceil(3) * 4,
+        Padding::NoPadding => (input_len * 8).div_ceil(6),
     }
 }
 
-pub fn encode_to_vec(config: &Base64Config, bytes: &[u8]) -> Vec<u8> {
-    let encoded_iter = encode(config, bytes);
-    Vec::from_iter(encoded_iter)
+/// Returns the number of bytes `base64_encoded_bytes` decodes to, from its
+/// length and trailing padding alone.
+pub const fn decoded_len(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+) -> Result<usize, Base64Error> {
+    let trailing_pad_count = match validate_padding(config, base64_encoded_bytes) {
+        Ok(count) => count,
+        Err(e) => return Err(e),
+    };
+    match validate_length(config, base64_encoded_bytes.len(), trailing_pad_count) {
+        Ok(unpadded_length) => Ok(unpadded_length * 6 / 8),
+        Err(e) => Err(e),
+    }
+}
+
+/// Encodes into an array, in const contexts too.
+///
+/// Panics if `N` is not [`encoded_len`].
+pub const fn encode_array<const N: usize>(config: &Base64Config, bytes: &[u8]) -> [u8; N] {
+    assert!(
+        N == encoded_len(config, bytes.len()),
+        "array length is not the encoded length"
+    );
+    let mut encoded = [0u8; N];
+    let mut encoder = Encoder::new(config);
+    let length = encoder.update(bytes, &mut encoded);
+    let (tail, tail_length) = encoder.finish();
+    let mut i = 0;
+    while i < tail_length {
+        encoded[length + i] = tail[i];
+        i += 1;
+    }
+    encoded
+}
+
+/// Decodes into an array, in const contexts too.
+///
+/// Panics if `N` is not [`decoded_len`].
+pub const fn decode_array<const N: usize>(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+) -> Result<[u8; N], Base64Error> {
+    match decoded_len(config, base64_encoded_bytes) {
+        Ok(length) => assert!(N == length, "array length is not the decoded length"),
+        Err(e) => return Err(e),
+    }
+    let mut decoded = [0u8; N];
+    let mut decoder = Decoder::new(config);
+    // complete quads never decode to more than `decoded_len` bytes
+    let length = match decoder.update(base64_encoded_bytes, &mut decoded) {
+        Ok(length) => length,
+        Err(e) => return Err(e),
+    };
+    let (tail, tail_length) = match decoder.finish() {
+        Ok(tail) => tail,
+        Err(e) => return Err(e),
+    };
+    let mut i = 0;
+    while i < tail_length {
+        decoded[length + i] = tail[i];
+        i += 1;
+    }
+    Ok(decoded)
+}
+
+/// Decodes a string literal at compile time into a `&'static [u8]`, with the
+/// standard alphabet unless a config is given first. Invalid base64 fails
+/// the build.
+///
+/// ```
+/// static KEY: &[u8] = base64::base64_decode!("3q2+7w==");
+/// assert_eq!(KEY, [0xde, 0xad, 0xbe, 0xef]);
+///
+/// let url = base64::base64_decode!(base64::base64::Base64Config::url(), "3q2-7w");
+/// assert_eq!(url, [0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ```compile_fail
+/// let key = base64::base64_decode!("3q2+7w=");
+/// ```
+#[macro_export]
+macro_rules! base64_decode {
+    (@bytes $config:expr, $encoded:expr) => {{
+        const __BASE64_CONFIG: $crate::base64::Base64Config = $config;
+        const __BASE64_ENCODED: &[u8] = $encoded;
+        const __BASE64_LEN: usize = $crate::base64::unwrap_literal($crate::base64::decoded_len(
+            &__BASE64_CONFIG,
+            __BASE64_ENCODED,
+        ));
+        static __BASE64_DECODED: [u8; __BASE64_LEN] = $crate::base64::unwrap_literal(
+            $crate::base64::decode_array(&__BASE64_CONFIG, __BASE64_ENCODED),
+        );
+        &__BASE64_DECODED as &'static [u8]
+    }};
+    ($config:expr, $encoded:expr) => {
+        $crate::base64_decode!(@bytes $config, ::core::primitive::str::as_bytes($encoded))
+    };
+    ($encoded:expr) => {
+        $crate::base64_decode!($crate::base64::Base64Config::standard(), $encoded)
+    };
+}
+
+/// Like [`base64_decode!`], but reads the literal from a file relative to the
+/// current source file, the way `include_bytes!` does. ASCII whitespace is
+/// skipped, so wrapped lines and a trailing newline are fine.
+#[macro_export]
+macro_rules! include_base64 {
+    ($config:expr, $path:expr) => {{
+        const __BASE64_FILE: &[u8] = include_bytes!($path);
+        const __BASE64_STRIPPED: [u8; $crate::base64::non_whitespace_len(__BASE64_FILE)] =
+            $crate::base64::strip_whitespace(__BASE64_FILE);
+        $crate::base64_decode!(@bytes $config, &__BASE64_STRIPPED)
+    }};
+    ($path:expr) => {
+        $crate::include_base64!($crate::base64::Base64Config::standard(), $path)
+    };
+}
+
+#[doc(hidden)]
+pub const fn non_whitespace_len(bytes: &[u8]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_whitespace() {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+#[doc(hidden)]
+pub const fn strip_whitespace<const N: usize>(bytes: &[u8]) -> [u8; N] {
+    let mut stripped = [0u8; N];
+    let mut count = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_whitespace() {
+            stripped[count] = bytes[i];
+            count += 1;
+        }
+        i += 1;
+    }
+    stripped
+}
+
+/// Turns a decoding error in a literal into a compile error. Const panics
+/// cannot format, so the message names the kind of error only.
+#[doc(hidden)]
+pub const fn unwrap_literal<T: Copy>(result: Result<T, Base64Error>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(Base64Error::InvalidCharacter(_) | Base64Error::InvalidSecretCharacter) => {
+            panic!("invalid base64 literal: invalid character")
+        }
+        Err(Base64Error::InvalidLength(..)) => {
+            panic!("invalid base64 literal: length not a multiple of 4, padding required")
+        }
+        Err(Base64Error::HasPaddingAndLengthNotMultipleOf4(_)) => {
+            panic!("invalid base64 literal: padding detected and length not a multiple of 4")
+        }
+        Err(Base64Error::TooManyPaddingCharacters(_)) => {
+            panic!("invalid base64 literal: too many padding characters")
+        }
+    }
 }
 
 #[cfg(test)]
+#[allow(clippy::redundant_guards)]
 mod tests;
@@ -0,0 +1,99 @@
+use super::{
+    Base64Config, Base64Error, Decoder, decode_to_slice, decode_to_vec, encode_to_slice,
+    encode_to_vec, encoded_len,
+};
+use std::thread;
+
+/// Splits `len` units into at most `threads` chunks of whole groups.
+fn chunk_len(len: usize, group_len: usize, threads: usize) -> usize {
+    len.div_ceil(group_len).div_ceil(threads).max(1) * group_len
+}
+
+/// Encodes on up to `threads` threads, each taking a run of whole triplets.
+/// The output is identical to [`encode_to_vec`]; 0 threads counts as 1.
+pub fn encode_parallel(config: &Base64Config, bytes: &[u8], threads: usize) -> Vec<u8> {
+    let chunk_len = chunk_len(bytes.len(), 3, threads.max(1));
+    if chunk_len >= bytes.len() {
+        return encode_to_vec(config, bytes);
+    }
+    let mut encoded = vec![0u8; encoded_len(config, bytes.len())];
+    thread::scope(|scope| {
+        // only the last chunk can end in a partial triplet, so only it gets padding
+        for (input, output) in bytes
+            .chunks(chunk_len)
+            .zip(encoded.chunks_mut(chunk_len / 3 * 4))
+        {
+            scope.spawn(|| encode_to_slice(config, input, output));
+        }
+    });
+    encoded
+}
+
+/// Decodes on up to `threads` threads, each taking a run of whole quads.
+/// The output, and the error for invalid input, are identical to
+/// [`decode_to_vec`]; 0 threads counts as 1.
+pub fn decode_parallel(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+    threads: usize,
+) -> Result<Vec<u8>, Base64Error> {
+    let chunk_len = chunk_len(base64_encoded_bytes.len(), 4, threads.max(1));
+    if chunk_len >= base64_encoded_bytes.len() {
+        return decode_to_vec(config, base64_encoded_bytes);
+    }
+    let mut decoded = vec![0u8; Decoder::max_output_len(base64_encoded_bytes.len())];
+    let chunk_count = base64_encoded_bytes.len().div_ceil(chunk_len);
+    let lengths: Vec<Option<usize>> = thread::scope(|scope| {
+        let workers: Vec<_> = base64_encoded_bytes
+            .chunks(chunk_len)
+            .zip(decoded.chunks_mut(chunk_len / 4 * 3))
+            .enumerate()
+            .map(|(i, (input, output))| {
+                scope.spawn(move || {
+                    if i + 1 == chunk_count {
+                        return decode_to_slice(config, input, output).ok();
+                    }
+                    // padding is only valid at the very end of the input
+                    let mut decoder = Decoder::new(config);
+                    let length = decoder.update(input, output).ok()?;
+                    (decoder.trailing_pad_count == 0).then_some(length)
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("decoder thread panicked"))
+            .collect()
+    });
+    let mut length = 0;
+    for (i, chunk_length) in lengths.into_iter().enumerate() {
+        match chunk_length {
+            Some(chunk_length) => length += chunk_length,
+            None => return Err(first_error(config, base64_encoded_bytes, i * chunk_len)),
+        }
+    }
+    decoded.truncate(length);
+    Ok(decoded)
+}
+
+/// Replays the sequential decoder from `offset`, where every chunk before
+/// decoded cleanly and without padding, to report the same error it would.
+fn first_error(config: &Base64Config, base64_encoded_bytes: &[u8], offset: usize) -> Base64Error {
+    let mut decoder = Decoder {
+        length: offset,
+        ..Decoder::new(config)
+    };
+    let mut scratch = [0u8; 6];
+    for quad in base64_encoded_bytes[offset..].chunks(4) {
+        if let Err(e) = decoder.update(quad, &mut scratch) {
+            return e;
+        }
+    }
+    match decoder.finish() {
+        Err(e) => e,
+        Ok(_) => unreachable!("a chunk failed, so the input from it onwards is invalid"),
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,78 @@
+use super::*;
+use crate::base64::Padding;
+
+fn configs() -> [Base64Config; 4] {
+    [
+        Base64Config::standard(),
+        Base64Config::url()
+            .with_padding(Padding::NoPadding)
+            .unwrap(),
+        Base64Config::mime(),
+        Base64Config::url(),
+    ]
+}
+
+#[test]
+fn test_encode_parallel_matches_sequential() {
+    let input: Vec<u8> = (0..=255).cycle().take(100).collect();
+    for config in configs() {
+        for length in 0..input.len() {
+            for threads in 0..6 {
+                assert_eq!(
+                    encode_parallel(&config, &input[..length], threads),
+                    encode_to_vec(&config, &input[..length]),
+                    "length {length}, {threads} threads"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_parallel_matches_sequential() {
+    let input: Vec<u8> = (0..=255).rev().cycle().take(100).collect();
+    for config in configs() {
+        for length in 0..input.len() {
+            let encoded = encode_to_vec(&config, &input[..length]);
+            for threads in 0..6 {
+                assert_eq!(
+                    decode_parallel(&config, &encoded, threads).unwrap(),
+                    &input[..length],
+                    "length {length}, {threads} threads"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_decode_parallel_reports_the_sequential_error() {
+    let valid = encode_to_vec(&Base64Config::standard(), &[0x5a; 40]);
+    let mut invalid_inputs = vec![
+        b"TWFu!WFu".to_vec(),
+        b"TWE=TWFu".to_vec(),
+        b"TWFuTW==TWFu".to_vec(),
+        b"TWFuTW==".to_vec(),
+        b"TWFuTW===".to_vec(),
+        b"TWFuT===TWFu".to_vec(),
+        b"TWFuTWFuT".to_vec(),
+        b"TWFuTWFuTW=".to_vec(),
+    ];
+    // an invalid symbol in each position, with more invalid ones after it
+    for i in 0..valid.len() {
+        let mut input = valid.clone();
+        input[i] = b'*';
+        input[valid.len() - 1] = b'?';
+        invalid_inputs.push(input);
+    }
+    for config in configs() {
+        for input in &invalid_inputs {
+            // some inputs are valid under some configs, so compare the whole result
+            let expected = format!("{:?}", decode_to_vec(&config, input));
+            for threads in 1..6 {
+                let actual = format!("{:?}", decode_parallel(&config, input, threads));
+                assert_eq!(actual, expected, "{threads} threads");
+            }
+        }
+    }
+}
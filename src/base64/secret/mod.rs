@@ -0,0 +1,62 @@
+use super::constant_time::{decode_to_slice, decoded_len};
+use super::{Base64Config, Base64Error};
+use alloc::vec::Vec;
+use core::sync::atomic::{Ordering, compiler_fence};
+
+/// Decoded key material. The buffer is allocated once at its final size
+/// and overwritten with zeroes when dropped; `Debug` shows only the length.
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl core::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "SecretBytes(<{} bytes redacted>)", self.0.len())
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        wipe_vec(&mut self.0);
+    }
+}
+
+/// Overwrites the whole allocation with zeroes, including spare capacity.
+/// The writes are volatile so they are not optimized away before the free.
+fn wipe_vec(vec: &mut Vec<u8>) {
+    let ptr = vec.as_mut_ptr();
+    for i in 0..vec.capacity() {
+        // SAFETY: the allocation is `capacity` bytes long and any byte
+        // pattern is a valid (if uninitialized) u8 slot to write to
+        unsafe { core::ptr::write_volatile(ptr.add(i), 0) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// Decodes with the constant-time codec straight into a [`SecretBytes`],
+/// so neither the result nor a partial result on error outlives its use.
+pub fn decode_secret(
+    config: &Base64Config,
+    base64_encoded_bytes: &[u8],
+) -> Result<SecretBytes, Base64Error> {
+    let mut secret = SecretBytes(alloc::vec![0u8; decoded_len(config, base64_encoded_bytes)?]);
+    decode_to_slice(config, base64_encoded_bytes, &mut secret.0)?;
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,55 @@
+use super::*;
+use crate::base64::constant_time::wipe;
+
+#[test]
+fn test_decode_secret() {
+    let config = Base64Config::standard();
+    let secret = decode_secret(&config, b"c2VjcmV0IGtleQ==").expect("decode should succeed");
+    assert_eq!(secret.as_ref(), b"secret key");
+    assert_eq!(secret.len(), 10);
+    assert!(!secret.is_empty());
+    // sized up front, so decoding never reallocated and left a copy behind
+    assert_eq!(secret.0.capacity(), secret.len());
+
+    let empty = decode_secret(&config, b"").expect("decode should succeed");
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_decode_secret_errors() {
+    let config = Base64Config::standard();
+    match decode_secret(&config, b"c2VjcmV0!GtleQ==") {
+        Err(Base64Error::InvalidSecretCharacter) => {}
+        other => panic!("expected InvalidSecretCharacter, got {:?}", other),
+    }
+    match decode_secret(&Base64Config::mime(), b"c2VjcmV0IGtleQ") {
+        Err(Base64Error::InvalidLength(14, b'=')) => {}
+        other => panic!("expected InvalidLength, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_debug_is_redacted() {
+    let secret = decode_secret(&Base64Config::standard(), b"c2VjcmV0").unwrap();
+    let debug = format!("{secret:?}");
+    assert_eq!(debug, "SecretBytes(<6 bytes redacted>)");
+    assert!(!format!("{:?}", Ok::<_, ()>(secret)).contains("secret"));
+}
+
+#[test]
+fn test_wipe() {
+    let mut bytes = *b"secret";
+    wipe(&mut bytes);
+    assert_eq!(bytes, [0u8; 6]);
+}
+
+#[test]
+fn test_wipe_vec_covers_spare_capacity() {
+    let mut vec = b"secret key".to_vec();
+    vec.truncate(6);
+    let capacity = vec.capacity();
+    wipe_vec(&mut vec);
+    // SAFETY: wipe_vec wrote every byte of the allocation
+    unsafe { vec.set_len(capacity) };
+    assert!(vec.iter().all(|b| *b == 0));
+}
@@ -1,4 +1,5 @@
 use super::*;
+use proptest::prelude::*;
 
 #[test]
 fn test_range_lengths_do_not_sum_to_64() {
@@ -14,10 +15,8 @@ fn test_range_lengths_do_not_sum_to_64() {
 #[test]
 fn test_padding_char_in_range() {
     // Create a config where the padding character '=' is inside a range.
-    let cfg = Base64Config {
-        ranges: vec![b'='..=b'='],
-        padding: Padding::Optional(b'='),
-    };
+    let cfg = Base64Config::unvalidated(vec![b'='..=b'='], Padding::Optional(b'='))
+        .expect("a single range fits");
     // validate_config should detect the padding char in a range
     match validate_config(&cfg) {
         Err(Base64ConfigError::PaddingCharInRange(c, _)) if c == b'=' => {}
@@ -25,6 +24,7 @@ fn test_padding_char_in_range() {
     }
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_invalid_character_detection() {
     let cfg = Base64Config::standard();
@@ -37,6 +37,7 @@ fn test_invalid_character_detection() {
     }
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_encode_decode_roundtrip_standard() {
     let cfg = Base64Config::standard();
@@ -46,6 +47,7 @@ fn test_encode_decode_roundtrip_standard() {
     assert_eq!(decoded, input);
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_decode_without_padding_optional() {
     let cfg = Base64Config::standard();
@@ -81,18 +83,62 @@ fn test_error_display_and_config_variants() {
     assert!(format!("{e4}").contains("Too many padding"));
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_ranges_are_stored_inline() {
+    // empty ranges add no symbols and take no slot
+    let cfg = Base64Config::new(
+        [
+            b'A'..=b'Z',
+            b'z'..=b'a',
+            b'a'..=b'z',
+            b'0'..=b'9',
+            b'+'..=b'+',
+            b'/'..=b'/',
+        ],
+        Padding::NoPadding,
+    )
+    .expect("valid config");
+    assert_eq!(cfg.ranges().len(), 5);
+    assert_eq!(encode_to_vec(&cfg, b"Hi?"), b"SGk/");
+
+    // 65 single-symbol ranges cannot all be stored, nor sum to 64
+    match Base64Config::new((0..=64u8).map(|b| b..=b), Padding::NoPadding) {
+        Err(Base64ConfigError::RangeLengthsDoNotSumTo64(65)) => {}
+        other => panic!(
+            "expected RangeLengthsDoNotSumTo64(65), got {:?}",
+            other.err()
+        ),
+    }
+}
+
+#[test]
+fn test_slice_functions() {
+    let cfg = Base64Config::standard();
+    let mut encoded = [0u8; 8];
+    let length = encode_to_slice(&cfg, b"Hello", &mut encoded);
+    assert_eq!(&encoded[..length], b"SGVsbG8=");
+    let mut decoded = [0u8; 9];
+    let length = decode_to_slice(&cfg, &encoded, &mut decoded).expect("valid input");
+    assert_eq!(&decoded[..length], b"Hello");
+    assert!(matches!(
+        decode_to_slice(&cfg, b"SGV!", &mut decoded),
+        Err(Base64Error::InvalidCharacter(b'!'))
+    ));
+}
+
 #[test]
 fn test_overlapping_ranges_validation() {
-    let cfg = Base64Config {
-        ranges: vec![b'A'..=b'Z', b'Z'..=b'z'],
-        padding: Padding::NoPadding,
-    };
+    let cfg = Base64Config::unvalidated(vec![b'A'..=b'Z', b'Z'..=b'z'], Padding::NoPadding)
+        .expect("two ranges fit");
     match validate_config(&cfg) {
         Err(Base64ConfigError::OverlappingRanges(_, _)) => {}
         other => panic!("expected overlapping ranges error, got {:?}", other),
     }
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_count_trailing_pad_characters_and_validate_errors() {
     let std = Base64Config::standard();
@@ -140,17 +186,6 @@ fn test_pack_unpack_roundtrip_and_byte_mappings() {
     assert_eq!(decode_byte(&std, b'/'), 63);
 }
 
-#[test]
-fn test_chunk_iter_partial() {
-    let v = vec![1u8, 2u8, 3u8, 4u8, 5u8];
-    let iter = v.into_iter();
-    let mut chunks = chunk_iter::<u8, 3, _>(&iter);
-    let first = chunks.next().expect("first chunk");
-    assert_eq!(first, [1u8, 2u8, 3u8]);
-    // there should be no second full chunk (only 2 elements left)
-    assert!(chunks.next().is_none());
-}
-
 #[test]
 fn test_config_constructors() {
     // call url and mime constructors to increase coverage
@@ -158,6 +193,24 @@ fn test_config_constructors() {
     let _ = Base64Config::mime();
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_with_padding() {
+    let cfg = Base64Config::url()
+        .with_padding(Padding::NoPadding)
+        .expect("valid config");
+    assert_eq!(encode_to_vec(&cfg, b"\xfb\xff"), b"-_8");
+    let cfg = Base64Config::url()
+        .with_padding(Padding::Required(b'='))
+        .expect("valid config");
+    assert_eq!(encode_to_vec(&cfg, b"\xfb\xff"), b"-_8=");
+    match Base64Config::url().with_padding(Padding::Optional(b'-')) {
+        Err(Base64ConfigError::PaddingCharInRange(b'-', _)) => {}
+        other => panic!("expected PaddingCharInRange('-'), got {:?}", other.err()),
+    }
+}
+
+#[cfg(feature = "alloc")]
 #[test]
 fn test_no_padding_count_and_encode_branch() {
     // construct a standard-like config but with NoPadding
@@ -185,18 +238,23 @@ fn test_no_padding_count_and_encode_branch() {
 }
 
 #[test]
-fn test_validate_direct_branches() {
+fn test_decoder_finish_validates_length() {
     // Required padding invalid length
     let mime = Base64Config::mime();
-    match validate_base64(&mime, b"AA") {
-        Err(Base64Error::InvalidLength(len, _)) if len == 2 => {}
+    let mut decoder = Decoder::new(&mime);
+    let mut output = [0u8; 3];
+    assert_eq!(decoder.update(b"AA", &mut output).unwrap(), 0);
+    match decoder.finish() {
+        Err(Base64Error::InvalidLength(2, _)) => {}
         other => panic!("expected InvalidLength, got {:?}", other),
     }
 
     // Optional padding with padding present but length not multiple of 4
     let std = Base64Config::standard();
-    match validate_base64(&std, b"AA=") {
-        Err(Base64Error::HasPaddingAndLengthNotMultipleOf4(len)) if len == 3 => {}
+    let mut decoder = Decoder::new(&std);
+    assert_eq!(decoder.update(b"AA=", &mut output).unwrap(), 0);
+    match decoder.finish() {
+        Err(Base64Error::HasPaddingAndLengthNotMultipleOf4(3)) => {}
         other => panic!(
             "expected HasPaddingAndLengthNotMultipleOf4, got {:?}",
             other
@@ -204,6 +262,91 @@ fn test_validate_direct_branches() {
     }
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn test_encoder_any_split_matches_one_shot() {
+    let input: Vec<u8> = (0..=255u8).rev().collect();
+    for cfg in [Base64Config::standard(), Base64Config::mime()] {
+        let expected = encode_to_vec(&cfg, &input);
+        for piece in 1..=7 {
+            let mut encoder = Encoder::new(&cfg);
+            let mut encoded = Vec::new();
+            for chunk in input.chunks(piece) {
+                let mut output = vec![0u8; Encoder::max_output_len(chunk.len())];
+                let length = encoder.update(chunk, &mut output);
+                encoded.extend_from_slice(&output[..length]);
+            }
+            let (tail, length) = encoder.finish();
+            encoded.extend_from_slice(&tail[..length]);
+            assert_eq!(encoded, expected, "pieces of {piece}");
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_decoder_any_split_matches_one_shot() {
+    let cfg = Base64Config::standard();
+    let input: Vec<u8> = (0..100u8).collect();
+    let encoded = encode_to_vec(&cfg, &input);
+    for piece in 1..=7 {
+        let mut decoder = Decoder::new(&cfg);
+        let mut decoded = Vec::new();
+        for chunk in encoded.chunks(piece) {
+            let mut output = vec![0u8; Decoder::max_output_len(chunk.len())];
+            let length = decoder.update(chunk, &mut output).expect("valid input");
+            decoded.extend_from_slice(&output[..length]);
+        }
+        let (tail, length) = decoder.finish().expect("valid input");
+        decoded.extend_from_slice(&tail[..length]);
+        assert_eq!(decoded, input, "pieces of {piece}");
+    }
+}
+
+#[test]
+fn test_encoder_finish() {
+    let std = Base64Config::standard();
+    let mut encoder = Encoder::new(&std);
+    let mut output = [0u8; 4];
+    assert_eq!(encoder.update(b"Ma", &mut output), 0);
+    assert_eq!(encoder.finish(), (*b"TWE=", 4));
+
+    let cfg = Base64Config::url()
+        .with_padding(Padding::NoPadding)
+        .expect("valid config");
+    let mut encoder = Encoder::new(&cfg);
+    assert_eq!(encoder.update(b"M", &mut output), 0);
+    let (symbols, length) = encoder.finish();
+    assert_eq!(&symbols[..length], b"TQ");
+    assert_eq!(Encoder::new(&cfg).finish().1, 0);
+}
+
+#[test]
+fn test_decoder_rejects_symbols_after_padding_across_updates() {
+    let std = Base64Config::standard();
+    let mut decoder = Decoder::new(&std);
+    let mut output = [0u8; 6];
+    assert_eq!(decoder.update(b"TWE", &mut output).unwrap(), 0);
+    assert_eq!(decoder.update(b"=", &mut output).unwrap(), 0);
+    match decoder.update(b"A", &mut output) {
+        Err(Base64Error::InvalidCharacter(b'A')) => {}
+        other => panic!("expected InvalidCharacter('A'), got {:?}", other),
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_iterators_match_vec_functions() {
+    let std = Base64Config::standard();
+    for length in 0..8 {
+        let input = &b"abcdefgh"[..length];
+        let encoded = Vec::from_iter(encode(&std, input));
+        assert_eq!(encoded, encode_to_vec(&std, input));
+        let decoded = Vec::from_iter(decode(&std, &encoded).expect("valid input"));
+        assert_eq!(decoded, input);
+    }
+}
+
 #[test]
 fn test_encode_byte_no_match_returns_zero() {
     let std = Base64Config::standard();
@@ -220,6 +363,7 @@ fn test_decode_byte_no_match_returns_zero() {
     assert_eq!(decode_byte(&std, b'?'), 0u8);
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_valid_optional_padding_decodes() {
     let cfg = Base64Config::standard(); // Padding::Optional
@@ -229,6 +373,7 @@ fn test_valid_optional_padding_decodes() {
     assert_eq!(decoded, b"Hello, World!");
 }
 
+#[cfg(feature = "alloc")]
 #[test]
 fn test_valid_required_padding_decodes() {
     let cfg = Base64Config::mime(); // Padding::Required
@@ -237,3 +382,367 @@ fn test_valid_required_padding_decodes() {
     let decoded = decode_to_vec(&cfg, b64).expect("should decode with required padding");
     assert_eq!(decoded, b"Ma");
 }
+
+#[test]
+fn test_from_ranges_matches_new() {
+    const URL: Base64Config = Base64Config::url();
+    let from_iter = Base64Config::new(URL.ranges().iter().cloned(), Padding::NoPadding).unwrap();
+    let from_slice = Base64Config::from_ranges(URL.ranges(), Padding::NoPadding).unwrap();
+    assert_eq!(from_iter.ranges(), from_slice.ranges());
+
+    match Base64Config::from_ranges(&[b'A'..=b'Z', b'M'..=b'Z'], Padding::NoPadding) {
+        Err(Base64ConfigError::OverlappingRanges(r1, r2)) => {
+            assert_eq!((r1, r2), (b'A'..=b'Z', b'M'..=b'Z'))
+        }
+        other => panic!("expected OverlappingRanges, got {:?}", other.err()),
+    }
+    match Base64Config::from_ranges(&[b'0'..=b'?'], Padding::Required(b'=')) {
+        Err(Base64ConfigError::PaddingCharInRange(b'=', _)) => {}
+        other => panic!("expected PaddingCharInRange, got {:?}", other.err()),
+    }
+    match Base64Config::from_ranges(&[0..=255], Padding::NoPadding) {
+        Err(Base64ConfigError::RangeLengthsDoNotSumTo64(256)) => {}
+        other => panic!(
+            "expected RangeLengthsDoNotSumTo64(256), got {:?}",
+            other.err()
+        ),
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_const_arrays_match_runtime() {
+    const STD: Base64Config = Base64Config::standard();
+    const ENCODED: [u8; 8] = encode_array(&STD, b"hello");
+    const DECODED: [u8; 5] = match decode_array(&STD, &ENCODED) {
+        Ok(decoded) => decoded,
+        Err(_) => panic!("invalid base64"),
+    };
+    assert_eq!(ENCODED, *b"aGVsbG8=");
+    assert_eq!(DECODED, *b"hello");
+
+    let unpadded = Base64Config::url()
+        .with_padding(Padding::NoPadding)
+        .unwrap();
+    for length in 0..8 {
+        let input = &b"\xfb\xff\xfe\x00\x01\x02\x03"[..length];
+        let encoded = encode_to_vec(&unpadded, input);
+        assert_eq!(encoded_len(&unpadded, length), encoded.len());
+        assert_eq!(decoded_len(&unpadded, &encoded).unwrap(), length);
+    }
+    assert_eq!(encode_array::<3>(&unpadded, b"\xfb\xff"), *b"-_8");
+    assert_eq!(decode_array::<2>(&unpadded, b"-_8").unwrap(), *b"\xfb\xff");
+}
+
+#[test]
+fn test_decode_array_errors() {
+    let std = Base64Config::standard();
+    match decode_array::<3>(&std, b"TW?u") {
+        Err(Base64Error::InvalidCharacter(b'?')) => {}
+        other => panic!("expected InvalidCharacter('?'), got {:?}", other),
+    }
+    match decode_array::<1>(&Base64Config::mime(), b"TQ") {
+        Err(Base64Error::InvalidLength(2, b'=')) => {}
+        other => panic!("expected InvalidLength(2, '='), got {:?}", other),
+    }
+    match decode_array::<1>(&std, b"TQ===") {
+        Err(Base64Error::TooManyPaddingCharacters(3)) => {}
+        other => panic!("expected TooManyPaddingCharacters(3), got {:?}", other),
+    }
+}
+
+#[test]
+#[should_panic(expected = "array length is not the decoded length")]
+fn test_decode_array_wrong_length_panics() {
+    let _ = decode_array::<4>(&Base64Config::standard(), b"TWFu");
+}
+
+#[test]
+fn test_literal_macros() {
+    static KEY: &[u8] = crate::base64_decode!("3q2+7w==");
+    assert_eq!(KEY, [0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(crate::base64_decode!(Base64Config::url(), "3q2-7w"), KEY);
+    assert_eq!(crate::base64_decode!(""), b"");
+
+    let included = crate::include_base64!("testdata/wrapped.b64");
+    assert_eq!(included, Vec::from_iter(0..100u8));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_rfc4648_section_10_vectors() {
+    let vectors: [(&[u8], &[u8]); 7] = [
+        (b"", b""),
+        (b"f", b"Zg=="),
+        (b"fo", b"Zm8="),
+        (b"foo", b"Zm9v"),
+        (b"foob", b"Zm9vYg=="),
+        (b"fooba", b"Zm9vYmE="),
+        (b"foobar", b"Zm9vYmFy"),
+    ];
+    let unpadded = Base64Config::url()
+        .with_padding(Padding::NoPadding)
+        .unwrap();
+    for (plain, encoded) in vectors {
+        for config in [
+            Base64Config::standard(),
+            Base64Config::url(),
+            Base64Config::mime(),
+        ] {
+            assert_eq!(encode_to_vec(&config, plain), encoded);
+            assert_eq!(decode_to_vec(&config, encoded).unwrap(), plain);
+        }
+        let trimmed = encoded
+            .strip_suffix(b"==")
+            .or(encoded.strip_suffix(b"="))
+            .unwrap_or(encoded);
+        assert_eq!(encode_to_vec(&unpadded, plain), trimmed);
+        assert_eq!(decode_to_vec(&unpadded, trimmed).unwrap(), plain);
+        assert_eq!(
+            decode_to_vec(&Base64Config::standard(), trimmed).unwrap(),
+            plain
+        );
+    }
+}
+
+/// A valid alphabet as disjoint ranges in random order, and the padding as
+/// a pad byte from outside the alphabet and whether it is required.
+#[derive(Debug, Clone)]
+struct ConfigSpec {
+    ranges: Vec<RangeInclusive<u8>>,
+    padding: Option<(u8, bool)>,
+}
+
+impl ConfigSpec {
+    fn config(&self) -> Base64Config {
+        let padding = match self.padding {
+            Some((p, true)) => Padding::Required(p),
+            Some((p, false)) => Padding::Optional(p),
+            None => Padding::NoPadding,
+        };
+        Base64Config::new(self.ranges.clone(), padding).expect("generated config is valid")
+    }
+
+    fn alphabet(&self) -> Vec<u8> {
+        self.ranges.iter().flat_map(|r| r.clone()).collect()
+    }
+}
+
+fn config_spec() -> impl Strategy<Value = ConfigSpec> {
+    // 65 distinct bytes: one becomes the pad byte, the rest the alphabet
+    let bytes = proptest::sample::subsequence((0..=255u8).collect::<Vec<_>>(), 65);
+    let splits = proptest::collection::vec(any::<bool>(), 64);
+    (
+        bytes,
+        any::<proptest::sample::Index>(),
+        splits,
+        any::<Option<bool>>(),
+    )
+        .prop_flat_map(|(mut bytes, pad_index, splits, required)| {
+            let pad = bytes.remove(pad_index.index(bytes.len()));
+            let mut ranges: Vec<RangeInclusive<u8>> = Vec::new();
+            for (b, split) in bytes.into_iter().zip(splits) {
+                match ranges.last_mut() {
+                    Some(r) if !split && *r.end() as u16 + 1 == b as u16 => *r = *r.start()..=b,
+                    _ => ranges.push(b..=b),
+                }
+            }
+            (
+                Just(ranges).prop_shuffle(),
+                Just(required.map(|required| (pad, required))),
+            )
+        })
+        .prop_map(|(ranges, padding)| ConfigSpec { ranges, padding })
+}
+
+/// Input close to valid: a valid encoding, shortened, given extra padding
+/// and with a few symbols replaced by other symbols, padding or stray bytes.
+fn config_and_input() -> impl Strategy<Value = (ConfigSpec, Vec<u8>)> {
+    config_spec()
+        .prop_flat_map(|spec| {
+            let pad = spec.padding.map_or(b'=', |(p, _)| p);
+            let symbol = prop_oneof![
+                4 => proptest::sample::select(spec.alphabet()),
+                2 => Just(pad),
+                1 => any::<u8>(),
+            ];
+            let edits = proptest::collection::vec((any::<proptest::sample::Index>(), symbol), 0..3);
+            let bytes = proptest::collection::vec(any::<u8>(), 0..32);
+            (Just(spec), bytes, 0..4usize, 0..4usize, edits)
+        })
+        .prop_map(|(spec, bytes, removed, added, edits)| {
+            let mut input = reference_encode(&spec, &bytes);
+            input.truncate(input.len().saturating_sub(removed));
+            let pad = spec.padding.map_or(b'=', |(p, _)| p);
+            input.extend(core::iter::repeat_n(pad, added));
+            for (index, symbol) in edits {
+                if !input.is_empty() {
+                    let i = index.index(input.len());
+                    input[i] = symbol;
+                }
+            }
+            (spec, input)
+        })
+}
+
+/// Straightforward bit-buffer codec to test against, written independently
+/// of the triplet packing above.
+fn reference_encode(spec: &ConfigSpec, bytes: &[u8]) -> Vec<u8> {
+    let alphabet = spec.alphabet();
+    let (mut buffer, mut bits, mut encoded) = (0u32, 0, Vec::new());
+    for b in bytes {
+        buffer = buffer << 8 | *b as u32;
+        bits += 8;
+        while bits >= 6 {
+            bits -= 6;
+            encoded.push(alphabet[(buffer >> bits) as usize & 63]);
+        }
+    }
+    if bits > 0 {
+        encoded.push(alphabet[(buffer << (6 - bits)) as usize & 63]);
+    }
+    if let Some((p, _)) = spec.padding {
+        while !encoded.len().is_multiple_of(4) {
+            encoded.push(p);
+        }
+    }
+    encoded
+}
+
+/// Accepts what the codec accepts: up to two trailing pad bytes, a length
+/// that is a multiple of 4 when padding is required or present, and a
+/// dangling sixth bit group that decodes to nothing.
+fn reference_decode(spec: &ConfigSpec, encoded: &[u8]) -> Option<Vec<u8>> {
+    let alphabet = spec.alphabet();
+    let mut body = encoded;
+    if let Some((p, required)) = spec.padding {
+        while let [rest @ .., last] = body
+            && *last == p
+        {
+            body = rest;
+        }
+        let pad_count = encoded.len() - body.len();
+        if pad_count > 2 || ((required || pad_count > 0) && !encoded.len().is_multiple_of(4)) {
+            return None;
+        }
+    }
+    let (mut buffer, mut bits, mut decoded) = (0u32, 0, Vec::new());
+    for symbol in body {
+        let value = alphabet.iter().position(|a| a == symbol)?;
+        buffer = buffer << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+    Some(decoded)
+}
+
+#[cfg(feature = "alloc")]
+proptest! {
+    #[test]
+    fn prop_presets_roundtrip(bytes in proptest::collection::vec(any::<u8>(), 0..256)) {
+        let unpadded = Base64Config::url().with_padding(Padding::NoPadding).unwrap();
+        for config in [Base64Config::standard(), Base64Config::url(), Base64Config::mime(), unpadded] {
+            let encoded = encode_to_vec(&config, &bytes);
+            prop_assert_eq!(Vec::from_iter(encode(&config, &bytes)), encoded.clone());
+            prop_assert_eq!(decode_to_vec(&config, &encoded).unwrap(), bytes.clone());
+            prop_assert_eq!(Vec::from_iter(decode(&config, &encoded).unwrap()), bytes.clone());
+        }
+    }
+
+    #[test]
+    fn prop_random_configs_roundtrip(
+        spec in config_spec(),
+        bytes in proptest::collection::vec(any::<u8>(), 0..64),
+    ) {
+        let config = spec.config();
+        let encoded = encode_to_vec(&config, &bytes);
+        prop_assert_eq!(decode_to_vec(&config, &encoded).unwrap(), bytes.clone());
+        prop_assert_eq!(constant_time::decode_to_vec(&config, &encoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn prop_encode_matches_reference(
+        spec in config_spec(),
+        bytes in proptest::collection::vec(any::<u8>(), 0..64),
+    ) {
+        let config = spec.config();
+        let expected = reference_encode(&spec, &bytes);
+        prop_assert_eq!(encode_to_vec(&config, &bytes), expected.clone());
+        prop_assert_eq!(constant_time::encode_to_vec(&config, &bytes), expected);
+    }
+
+    #[test]
+    fn prop_decode_matches_reference((spec, input) in config_and_input()) {
+        let config = spec.config();
+        let expected = reference_decode(&spec, &input);
+        prop_assert_eq!(decode_to_vec(&config, &input).ok(), expected.clone());
+        prop_assert_eq!(constant_time::decode_to_vec(&config, &input).ok(), expected);
+    }
+}
+
+// the slice functions on their own, so a core-only build is tested as well
+proptest! {
+    #[test]
+    fn prop_slice_functions_match_reference((spec, input) in config_and_input()) {
+        let config = spec.config();
+        let mut output = [0u8; 64];
+        let expected = reference_decode(&spec, &input);
+        let length = decode_to_slice(&config, &input, &mut output);
+        prop_assert_eq!(length.ok().map(|n| output[..n].to_vec()), expected.clone());
+        let length = constant_time::decode_to_slice(&config, &input, &mut output);
+        prop_assert_eq!(length.ok().map(|n| output[..n].to_vec()), expected.clone());
+
+        if let Some(bytes) = expected {
+            let expected = reference_encode(&spec, &bytes);
+            let length = encode_to_slice(&config, &bytes, &mut output);
+            prop_assert_eq!(&output[..length], &expected[..]);
+            let length = constant_time::encode_to_slice(&config, &bytes, &mut output);
+            prop_assert_eq!(&output[..length], &expected[..]);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_alphabet_at_the_top_of_the_byte_range() {
+    // symbol arithmetic must not wrap when a range ends at 255
+    let config = Base64Config::new([192..=255], Padding::Required(0)).unwrap();
+    assert_eq!(decode_symbol(&config, 255).unwrap(), 63);
+    assert_eq!(encode_byte(&config, 63), 255);
+    match decode_symbol(&config, 191) {
+        Err(Base64Error::InvalidCharacter(191)) => {}
+        other => panic!("expected InvalidCharacter(191), got {:?}", other),
+    }
+    let bytes: Vec<u8> = (0..=255).collect();
+    let encoded = encode_to_vec(&config, &bytes[..10]);
+    assert_eq!(&encoded[14..], [0, 0]);
+    assert_eq!(decode_to_vec(&config, &encoded).unwrap(), &bytes[..10]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_sixty_four_single_byte_ranges() {
+    // the largest number of ranges, with offsets summing right up to 63
+    let config = Base64Config::new(
+        (0..64).rev().map(|i| 4 * i + 3..=4 * i + 3),
+        Padding::Optional(0),
+    )
+    .unwrap();
+    assert_eq!(config.ranges().len(), 64);
+    assert_eq!(decode_symbol(&config, 255).unwrap(), 0);
+    assert_eq!(decode_symbol(&config, 3).unwrap(), 63);
+    let bytes: Vec<u8> = (0..=255).collect();
+    let encoded = encode_to_vec(&config, &bytes);
+    assert_eq!(decode_to_vec(&config, &encoded).unwrap(), bytes);
+
+    match Base64Config::new((0..65).map(|i| i..=i), Padding::NoPadding) {
+        Err(Base64ConfigError::RangeLengthsDoNotSumTo64(65)) => {}
+        other => panic!(
+            "expected RangeLengthsDoNotSumTo64(65), got {:?}",
+            other.err()
+        ),
+    }
+}
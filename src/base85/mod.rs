@@ -0,0 +1,228 @@
+use std::error;
+
+const ASCII85_ALPHABET: &[u8; 85] =
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const RFC1924_ALPHABET: &[u8; 85] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+const ASCII85_PREFIX: &[u8] = b"<~";
+const ASCII85_SUFFIX: &[u8] = b"~>";
+const ASCII85_ZERO_GROUP: u8 = b'z';
+
+#[derive(Clone, Copy)]
+pub enum Base85Variant {
+    /// Adobe Ascii85: `!`..=`u`, `<~ ~>` delimiters and `z` for all-zero groups
+    Ascii85,
+    /// ZeroMQ Z85
+    Z85,
+    /// RFC 1924 alphabet, as used by git binary patches
+    Rfc1924,
+}
+
+#[derive(Debug)]
+pub enum Base85Error {
+    InvalidCharacter(u8),
+    InvalidLength(usize),
+    GroupOverflow([u8; 5]),
+    MisplacedZeroGroup(usize),
+    MissingDelimiter(&'static str),
+}
+
+impl error::Error for Base85Error {}
+
+impl std::fmt::Display for Base85Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base85Error::InvalidCharacter(c) => {
+                write!(f, "Invalid character \'{}\'", *c as char)
+            }
+            Base85Error::InvalidLength(length) => {
+                write!(
+                    f,
+                    "Length {} leaves a final group of a single symbol",
+                    *length
+                )
+            }
+            Base85Error::GroupOverflow(group) => {
+                write!(
+                    f,
+                    "Group \"{}\" exceeds the 32-bit range",
+                    String::from_utf8_lossy(group)
+                )
+            }
+            Base85Error::MisplacedZeroGroup(position) => {
+                write!(
+                    f,
+                    "Zero group shortcut \'z\' inside a group at position {}",
+                    *position
+                )
+            }
+            Base85Error::MissingDelimiter(delimiter) => {
+                write!(f, "Missing delimiter \"{}\"", *delimiter)
+            }
+        }
+    }
+}
+
+impl Base85Variant {
+    fn alphabet(&self) -> &'static [u8; 85] {
+        match self {
+            Base85Variant::Ascii85 => ASCII85_ALPHABET,
+            Base85Variant::Z85 => Z85_ALPHABET,
+            Base85Variant::Rfc1924 => RFC1924_ALPHABET,
+        }
+    }
+
+    fn delimiters(&self) -> (&'static [u8], &'static [u8]) {
+        match self {
+            Base85Variant::Ascii85 => (ASCII85_PREFIX, ASCII85_SUFFIX),
+            Base85Variant::Z85 | Base85Variant::Rfc1924 => (&[], &[]),
+        }
+    }
+
+    fn has_zero_group_shortcut(&self) -> bool {
+        matches!(self, Base85Variant::Ascii85)
+    }
+}
+
+fn decode_symbol(variant: Base85Variant, b: u8) -> Option<u8> {
+    variant
+        .alphabet()
+        .iter()
+        .position(|c| *c == b)
+        .map(|digit| digit as u8)
+}
+
+fn unpack_group(variant: Base85Variant, raw_group: &[u8]) -> ([u8; 5], usize) {
+    let mut padded_group = [0u8; 4];
+    padded_group[..raw_group.len()].copy_from_slice(raw_group);
+    let mut number = u32::from_be_bytes(padded_group);
+    if variant.has_zero_group_shortcut() && raw_group.len() == 4 && number == 0 {
+        return ([ASCII85_ZERO_GROUP, 0, 0, 0, 0], 1);
+    }
+    let alphabet = variant.alphabet();
+    let mut encoded_group = [0u8; 5];
+    for symbol in encoded_group.iter_mut().rev() {
+        *symbol = alphabet[(number % 85) as usize];
+        number /= 85;
+    }
+    (encoded_group, raw_group.len() + 1)
+}
+
+fn pack_group(encoded_group: &[u8; 5]) -> Option<[u8; 4]> {
+    let number = encoded_group
+        .iter()
+        .fold(0u64, |number, digit| number * 85 + *digit as u64);
+    let number = u32::try_from(number).ok()?;
+    Some(number.to_be_bytes())
+}
+
+fn strip_delimiters(
+    variant: Base85Variant,
+    base85_encoded_bytes: &[u8],
+) -> Result<&[u8], Base85Error> {
+    let (prefix, suffix) = variant.delimiters();
+    let trimmed = base85_encoded_bytes.trim_ascii();
+    // delimiters are optional, but one without the other is an error
+    match (trimmed.strip_prefix(prefix), trimmed.ends_with(suffix)) {
+        (Some(body), _) => body
+            .strip_suffix(suffix)
+            .ok_or(Base85Error::MissingDelimiter("~>")),
+        (None, true) => Err(Base85Error::MissingDelimiter("<~")),
+        (None, false) => Ok(trimmed),
+    }
+}
+
+fn decode_groups(
+    variant: Base85Variant,
+    symbols: impl Iterator<Item = u8>,
+) -> impl Iterator<Item = Result<([u8; 4], usize), Base85Error>> {
+    let mut symbols = symbols.enumerate();
+    std::iter::from_fn(move || {
+        let (position, first) = symbols.next()?;
+        if variant.has_zero_group_shortcut() && first == ASCII85_ZERO_GROUP {
+            return Some(Ok(([0u8; 4], 4)));
+        }
+        let mut raw_group = [first, 0, 0, 0, 0];
+        let mut length = 1;
+        while length < 5 {
+            match symbols.next() {
+                Some((position, ASCII85_ZERO_GROUP)) if variant.has_zero_group_shortcut() => {
+                    return Some(Err(Base85Error::MisplacedZeroGroup(position)));
+                }
+                Some((_, symbol)) => {
+                    raw_group[length] = symbol;
+                    length += 1;
+                }
+                None => break,
+            }
+        }
+        if length == 1 {
+            return Some(Err(Base85Error::InvalidLength(position + 1)));
+        }
+        let mut encoded_group = [84u8; 5];
+        for (digit, symbol) in encoded_group.iter_mut().zip(&raw_group[..length]) {
+            match decode_symbol(variant, *symbol) {
+                Some(d) => *digit = d,
+                None => return Some(Err(Base85Error::InvalidCharacter(*symbol))),
+            }
+        }
+        match pack_group(&encoded_group) {
+            Some(bytes) => Some(Ok((bytes, length - 1))),
+            None => Some(Err(Base85Error::GroupOverflow(raw_group))),
+        }
+    })
+}
+
+fn significant_symbols<'a>(
+    variant: Base85Variant,
+    body: &'a [u8],
+) -> impl Iterator<Item = u8> + Clone + use<'a> {
+    let skip_whitespace = matches!(variant, Base85Variant::Ascii85);
+    body.iter()
+        .copied()
+        .filter(move |b| !(skip_whitespace && b.is_ascii_whitespace()))
+}
+
+pub fn decode(
+    variant: Base85Variant,
+    base85_encoded_bytes: &[u8],
+) -> Result<impl Iterator<Item = u8> + use<'_>, Base85Error> {
+    let body = strip_delimiters(variant, base85_encoded_bytes)?;
+    let symbols = significant_symbols(variant, body);
+    decode_groups(variant, symbols.clone()).try_for_each(|group| group.map(|_| ()))?;
+    Ok(decode_groups(variant, symbols)
+        .map_while(|group| group.ok())
+        .flat_map(|(bytes, length)| bytes.into_iter().take(length)))
+}
+
+pub fn decode_to_vec(
+    variant: Base85Variant,
+    base85_encoded_bytes: &[u8],
+) -> Result<Vec<u8>, Base85Error> {
+    let decoded_iter = decode(variant, base85_encoded_bytes)?;
+    Ok(Vec::from_iter(decoded_iter))
+}
+
+pub fn encode(variant: Base85Variant, bytes: &[u8]) -> impl Iterator<Item = u8> + use<'_> {
+    let (prefix, suffix) = variant.delimiters();
+    let encoded_groups = bytes
+        .chunks(4)
+        .map(move |chunk| unpack_group(variant, chunk))
+        .flat_map(|(group, length)| group.into_iter().take(length));
+    prefix
+        .iter()
+        .copied()
+        .chain(encoded_groups)
+        .chain(suffix.iter().copied())
+}
+
+pub fn encode_to_vec(variant: Base85Variant, bytes: &[u8]) -> Vec<u8> {
+    let encoded_iter = encode(variant, bytes);
+    Vec::from_iter(encoded_iter)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,123 @@
+use super::*;
+
+#[test]
+fn test_ascii85_known_vector() {
+    let encoded = encode_to_vec(Base85Variant::Ascii85, b"Man is distinguished");
+    assert_eq!(encoded, b"<~9jqo^BlbD-BleB1DJ+*+F(f,q~>");
+    let decoded = decode_to_vec(Base85Variant::Ascii85, &encoded).expect("decode should succeed");
+    assert_eq!(decoded, b"Man is distinguished");
+}
+
+#[test]
+fn test_ascii85_zero_group_shortcut() {
+    let encoded = encode_to_vec(Base85Variant::Ascii85, b"\0\0\0\0ab");
+    assert_eq!(encoded, b"<~z@:B~>");
+    let decoded = decode_to_vec(Base85Variant::Ascii85, &encoded).expect("decode should succeed");
+    assert_eq!(decoded, b"\0\0\0\0ab");
+
+    // a partial final group of zeroes is not shortened
+    let encoded = encode_to_vec(Base85Variant::Ascii85, b"\0\0");
+    assert_eq!(encoded, b"<~!!!~>");
+}
+
+#[test]
+fn test_ascii85_without_delimiters_and_with_whitespace() {
+    let decoded = decode_to_vec(Base85Variant::Ascii85, b"9jqo^BlbD-\n  BleB1DJ+*+F(f,q")
+        .expect("decode should succeed");
+    assert_eq!(decoded, b"Man is distinguished");
+}
+
+#[test]
+fn test_ascii85_missing_end_delimiter() {
+    match decode_to_vec(Base85Variant::Ascii85, b"<~9jqo^") {
+        Err(Base85Error::MissingDelimiter("~>")) => {}
+        other => panic!("expected MissingDelimiter, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_ascii85_missing_start_delimiter() {
+    match decode_to_vec(Base85Variant::Ascii85, b"9jqo^~>") {
+        Err(Base85Error::MissingDelimiter("<~")) => {}
+        other => panic!("expected MissingDelimiter(\"<~\"), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_ascii85_misplaced_zero_group() {
+    match decode_to_vec(Base85Variant::Ascii85, b"<~9jzo^~>") {
+        Err(Base85Error::MisplacedZeroGroup(2)) => {}
+        other => panic!("expected MisplacedZeroGroup(2), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_z85_known_vector() {
+    let raw = [0x86u8, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B];
+    let encoded = encode_to_vec(Base85Variant::Z85, &raw);
+    assert_eq!(encoded, b"HelloWorld");
+    let decoded = decode_to_vec(Base85Variant::Z85, &encoded).expect("decode should succeed");
+    assert_eq!(decoded, raw);
+}
+
+#[test]
+fn test_rfc1924_known_vectors() {
+    let encoded = encode_to_vec(Base85Variant::Rfc1924, b"Hello, World!");
+    assert_eq!(encoded, b"NM&qnZ!92JZ*pv8Ap");
+    let decoded = decode_to_vec(Base85Variant::Rfc1924, &encoded).expect("decode should succeed");
+    assert_eq!(decoded, b"Hello, World!");
+
+    assert_eq!(encode_to_vec(Base85Variant::Rfc1924, b"abc"), b"VPaz");
+}
+
+#[test]
+fn test_partial_final_groups_roundtrip() {
+    let input = b"abcdefg";
+    for variant in [
+        Base85Variant::Ascii85,
+        Base85Variant::Z85,
+        Base85Variant::Rfc1924,
+    ] {
+        for length in 0..=input.len() {
+            let encoded = encode_to_vec(variant, &input[..length]);
+            let decoded = decode_to_vec(variant, &encoded).expect("decode should succeed");
+            assert_eq!(decoded, &input[..length]);
+        }
+    }
+}
+
+#[test]
+fn test_single_symbol_final_group() {
+    match decode_to_vec(Base85Variant::Z85, b"HelloW") {
+        Err(Base85Error::InvalidLength(6)) => {}
+        other => panic!("expected InvalidLength(6), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_character() {
+    match decode_to_vec(Base85Variant::Z85, b"Hello~orld") {
+        Err(Base85Error::InvalidCharacter(b'~')) => {}
+        other => panic!("expected InvalidCharacter('~'), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_group_overflow() {
+    // "s8W-\"" is 2^32 in Ascii85, one more than fits into a group
+    match decode_to_vec(Base85Variant::Ascii85, b"s8W-\"") {
+        Err(Base85Error::GroupOverflow(group)) if &group == b"s8W-\"" => {}
+        other => panic!("expected GroupOverflow, got {:?}", other),
+    }
+    let decoded = decode_to_vec(Base85Variant::Ascii85, b"s8W-!").expect("u32::MAX decodes");
+    assert_eq!(decoded, [0xFF; 4]);
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", Base85Error::InvalidCharacter(b'~')).contains("Invalid character"));
+    assert!(format!("{}", Base85Error::InvalidLength(6)).contains("Length 6"));
+    assert!(format!("{}", Base85Error::GroupOverflow(*b"s8W-\"")).contains("32-bit"));
+    assert!(format!("{}", Base85Error::MisplacedZeroGroup(2)).contains("position 2"));
+    assert!(format!("{}", Base85Error::MissingDelimiter("~>")).contains("~>"));
+}
@@ -0,0 +1,177 @@
+use std::error;
+
+const BASE91_ALPHABET: &[u8; 91] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789!#$%&()*+,./:;<=>?@[]^_`{|}~\"";
+
+/// Values up to this bound fit into 13 bits with room to spare, so they are
+/// extended to 14 bits to make use of the full 91 * 91 symbol pair range.
+const MAX_13_BIT_EXTENSIBLE: u32 = 88;
+const MASK_13_BITS: u32 = (1 << 13) - 1;
+const MASK_14_BITS: u32 = (1 << 14) - 1;
+
+#[derive(Debug)]
+pub enum Base91Error {
+    InvalidCharacter(u8),
+}
+
+impl error::Error for Base91Error {}
+
+impl std::fmt::Display for Base91Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Base91Error::InvalidCharacter(c) => {
+                write!(f, "Invalid character \'{}\'", *c as char)
+            }
+        }
+    }
+}
+
+/// Streaming basE91 encoder: bytes are pushed one at a time and symbol pairs
+/// are emitted as soon as 13 or 14 bits are queued.
+#[derive(Default)]
+pub struct Base91Encoder {
+    queue: u32,
+    num_bits: u32,
+}
+
+/// Streaming basE91 decoder: symbols are pushed one at a time and bytes are
+/// emitted whenever a symbol pair completes.
+#[derive(Default)]
+pub struct Base91Decoder {
+    queue: u32,
+    num_bits: u32,
+    pending_value: Option<u32>,
+}
+
+impl Base91Encoder {
+    pub fn push(&mut self, byte: u8) -> Option<[u8; 2]> {
+        self.queue |= (byte as u32) << self.num_bits;
+        self.num_bits += 8;
+        if self.num_bits <= 13 {
+            return None;
+        }
+        let mut value = self.queue & MASK_13_BITS;
+        if value > MAX_13_BIT_EXTENSIBLE {
+            self.queue >>= 13;
+            self.num_bits -= 13;
+        } else {
+            value = self.queue & MASK_14_BITS;
+            self.queue >>= 14;
+            self.num_bits -= 14;
+        }
+        Some(encode_pair(value))
+    }
+
+    pub fn finish(self) -> ([u8; 2], usize) {
+        if self.num_bits == 0 {
+            return ([0u8; 2], 0);
+        }
+        let length = if self.num_bits > 7 || self.queue > 90 {
+            2
+        } else {
+            1
+        };
+        (encode_pair(self.queue), length)
+    }
+}
+
+impl Base91Decoder {
+    pub fn push(&mut self, symbol: u8) -> Result<([u8; 2], usize), Base91Error> {
+        let digit = decode_symbol(symbol)?;
+        let Some(low_digit) = self.pending_value.take() else {
+            self.pending_value = Some(digit);
+            return Ok(([0u8; 2], 0));
+        };
+        let value = low_digit + digit * 91;
+        self.queue |= value << self.num_bits;
+        self.num_bits += if value & MASK_13_BITS > MAX_13_BIT_EXTENSIBLE {
+            13
+        } else {
+            14
+        };
+        let mut bytes = [0u8; 2];
+        let mut length = 0;
+        while self.num_bits > 7 {
+            bytes[length] = self.queue as u8;
+            length += 1;
+            self.queue >>= 8;
+            self.num_bits -= 8;
+        }
+        Ok((bytes, length))
+    }
+
+    pub fn finish(self) -> Option<u8> {
+        self.pending_value
+            .map(|value| (self.queue | value << self.num_bits) as u8)
+    }
+}
+
+fn encode_pair(value: u32) -> [u8; 2] {
+    [
+        BASE91_ALPHABET[(value % 91) as usize],
+        BASE91_ALPHABET[(value / 91 % 91) as usize],
+    ]
+}
+
+fn decode_symbol(b: u8) -> Result<u32, Base91Error> {
+    BASE91_ALPHABET
+        .iter()
+        .position(|c| *c == b)
+        .map(|digit| digit as u32)
+        .ok_or(Base91Error::InvalidCharacter(b))
+}
+
+fn validate_base91(base91_encoded_bytes: &[u8]) -> Result<(), Base91Error> {
+    base91_encoded_bytes
+        .iter()
+        .try_for_each(|b| decode_symbol(*b).map(|_| ()))
+}
+
+pub fn decode(
+    base91_encoded_bytes: &[u8],
+) -> Result<impl Iterator<Item = u8> + use<'_>, Base91Error> {
+    validate_base91(base91_encoded_bytes)?;
+    let mut decoder = Some(Base91Decoder::default());
+    let mut symbols = base91_encoded_bytes.iter();
+    Ok(std::iter::from_fn(move || {
+        let state = decoder.as_mut()?;
+        for symbol in symbols.by_ref() {
+            match state.push(*symbol) {
+                Ok((_, 0)) => {}
+                Ok(group) => return Some(group),
+                Err(_) => return None,
+            }
+        }
+        let last_byte = decoder.take()?.finish();
+        Some(([last_byte.unwrap_or(0), 0], last_byte.iter().count()))
+    })
+    .flat_map(|(group, length)| group.into_iter().take(length)))
+}
+
+pub fn decode_to_vec(base91_encoded_bytes: &[u8]) -> Result<Vec<u8>, Base91Error> {
+    let decoded_iter = decode(base91_encoded_bytes)?;
+    Ok(Vec::from_iter(decoded_iter))
+}
+
+pub fn encode(bytes: &[u8]) -> impl Iterator<Item = u8> + use<'_> {
+    let mut encoder = Some(Base91Encoder::default());
+    let mut input = bytes.iter();
+    std::iter::from_fn(move || {
+        let state = encoder.as_mut()?;
+        for byte in input.by_ref() {
+            if let Some(pair) = state.push(*byte) {
+                return Some((pair, 2));
+            }
+        }
+        Some(encoder.take()?.finish())
+    })
+    .flat_map(|(group, length)| group.into_iter().take(length))
+}
+
+pub fn encode_to_vec(bytes: &[u8]) -> Vec<u8> {
+    let encoded_iter = encode(bytes);
+    Vec::from_iter(encoded_iter)
+}
+
+#[cfg(test)]
+mod tests;
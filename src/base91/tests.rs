@@ -0,0 +1,101 @@
+use super::*;
+use crate::base64::{self, Base64Config};
+
+/// Small deterministic xorshift generator so the property tests need no extra dependencies.
+fn pseudo_random_bytes(seed: u64, length: usize) -> Vec<u8> {
+    let mut state = seed | 1;
+    (0..length)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_known_vectors() {
+    assert_eq!(encode_to_vec(b""), b"");
+    assert_eq!(encode_to_vec(b"test"), b"fPNKd");
+    assert_eq!(encode_to_vec(b"Hello, World!"), b">OwJh>}AQ;r@@Y?F");
+    assert_eq!(encode_to_vec(&[0xFF]), b"/C");
+    let decoded = decode_to_vec(b">OwJh>}AQ;r@@Y?F").expect("decode should succeed");
+    assert_eq!(decoded, b"Hello, World!");
+}
+
+#[test]
+fn test_invalid_character() {
+    // '-' and '\'' are not part of the basE91 alphabet
+    match decode_to_vec(b"fPN-d") {
+        Err(Base91Error::InvalidCharacter(b'-')) => {}
+        other => panic!("expected InvalidCharacter('-'), got {:?}", other),
+    }
+    assert!(format!("{}", Base91Error::InvalidCharacter(b'\'')).contains("Invalid character"));
+}
+
+#[test]
+fn test_streaming_state_machines() {
+    let mut encoder = Base91Encoder::default();
+    let mut encoded = Vec::new();
+    for byte in b"Hello, World!" {
+        if let Some(pair) = encoder.push(*byte) {
+            encoded.extend(pair);
+        }
+    }
+    let (last, length) = encoder.finish();
+    encoded.extend(&last[..length]);
+    assert_eq!(encoded, b">OwJh>}AQ;r@@Y?F");
+
+    let mut decoder = Base91Decoder::default();
+    let mut decoded = Vec::new();
+    for symbol in &encoded {
+        let (bytes, length) = decoder.push(*symbol).expect("valid symbol");
+        decoded.extend(&bytes[..length]);
+    }
+    decoded.extend(decoder.finish());
+    assert_eq!(decoded, b"Hello, World!");
+}
+
+#[test]
+fn test_roundtrip_random_inputs() {
+    for length in 0..200 {
+        let input = pseudo_random_bytes(length as u64 + 1, length);
+        let encoded = encode_to_vec(&input);
+        let decoded = decode_to_vec(&encoded).expect("decode should succeed");
+        assert_eq!(decoded, input, "roundtrip failed for length {length}");
+    }
+}
+
+#[test]
+fn test_roundtrip_degenerate_inputs() {
+    // all-zero and all-one inputs exercise the 13-bit and 14-bit branches respectively
+    for byte in [0x00u8, 0xFF] {
+        for length in 0..64 {
+            let input = vec![byte; length];
+            let decoded = decode_to_vec(&encode_to_vec(&input)).expect("decode should succeed");
+            assert_eq!(decoded, input);
+        }
+    }
+}
+
+#[test]
+fn test_output_size_against_base64() {
+    let config = Base64Config::standard();
+    for length in 0..1024 {
+        let input = pseudo_random_bytes(0x5EED ^ length as u64, length);
+        let base91_length = encode_to_vec(&input).len();
+        let base64_length = base64::encode_to_vec(&config, &input).len();
+        assert!(
+            base91_length <= base64_length,
+            "basE91 output ({base91_length}) longer than base64 ({base64_length}) for length {length}"
+        );
+        // every 13 input bits produce at most 2 symbols
+        assert!(base91_length <= (length * 8).div_ceil(13) * 2);
+    }
+    let input = pseudo_random_bytes(42, 1 << 16);
+    let base91_overhead = encode_to_vec(&input).len() as f64 / input.len() as f64;
+    let base64_overhead = base64::encode_to_vec(&config, &input).len() as f64 / input.len() as f64;
+    assert!(base91_overhead < 1.24, "basE91 overhead {base91_overhead}");
+    assert!(base91_overhead < base64_overhead);
+}
@@ -0,0 +1,115 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use std::error;
+
+const SCHEME: &str = "Basic";
+
+pub struct Credentials {
+    pub user_id: String,
+    pub password: String,
+}
+
+#[derive(Debug)]
+pub enum BasicAuthError {
+    WrongScheme(String),
+    MissingCredentials,
+    InvalidBase64(Base64Error),
+    InvalidUtf8,
+    MissingColon,
+    ControlCharacter,
+    UserIdContainsColon,
+}
+
+impl error::Error for BasicAuthError {}
+
+impl std::fmt::Display for BasicAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BasicAuthError::WrongScheme(scheme) => {
+                write!(f, "Expected the Basic scheme, got \"{}\"", scheme)
+            }
+            BasicAuthError::MissingCredentials => write!(f, "No credentials after scheme"),
+            BasicAuthError::InvalidBase64(e) => write!(f, "Invalid base64 credentials: {}", e),
+            BasicAuthError::InvalidUtf8 => write!(f, "Credentials are not valid UTF-8"),
+            BasicAuthError::MissingColon => {
+                write!(f, "Credentials lack the ':' between user-id and password")
+            }
+            BasicAuthError::ControlCharacter => {
+                write!(f, "Credentials contain a control character")
+            }
+            BasicAuthError::UserIdContainsColon => write!(f, "User-id must not contain ':'"),
+        }
+    }
+}
+
+// never print passwords, e.g. in logged request structs
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("user_id", &self.user_id)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+fn validate_text(text: &str) -> Result<(), BasicAuthError> {
+    if text.chars().any(char::is_control) {
+        return Err(BasicAuthError::ControlCharacter);
+    }
+    Ok(())
+}
+
+impl Credentials {
+    pub fn new(user_id: &str, password: &str) -> Result<Self, BasicAuthError> {
+        if user_id.contains(':') {
+            return Err(BasicAuthError::UserIdContainsColon);
+        }
+        validate_text(user_id)?;
+        validate_text(password)?;
+        Ok(Credentials {
+            user_id: user_id.to_string(),
+            password: password.to_string(),
+        })
+    }
+
+    /// The value for an `Authorization` header, e.g. `Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==`.
+    pub fn header_value(&self) -> String {
+        let user_pass = format!("{}:{}", self.user_id, self.password);
+        let token = encode_to_vec(&Base64Config::standard(), user_pass.as_bytes());
+        format!("{SCHEME} {}", String::from_utf8_lossy(&token))
+    }
+}
+
+/// Parses an `Authorization` header value. Credentials must be UTF-8, the
+/// only charset RFC 7617 allows servers to announce.
+pub fn parse_header_value(value: &str) -> Result<Credentials, BasicAuthError> {
+    let value = value.trim();
+    let (scheme, token) = value.split_once(' ').unwrap_or((value, ""));
+    if !scheme.eq_ignore_ascii_case(SCHEME) {
+        return Err(BasicAuthError::WrongScheme(scheme.to_string()));
+    }
+    let token = token.trim_start();
+    if token.is_empty() {
+        return Err(BasicAuthError::MissingCredentials);
+    }
+    let user_pass = decode_to_vec(&Base64Config::standard(), token.as_bytes())
+        .map_err(BasicAuthError::InvalidBase64)?;
+    let user_pass = String::from_utf8(user_pass).map_err(|_| BasicAuthError::InvalidUtf8)?;
+    let (user_id, password) = user_pass
+        .split_once(':')
+        .ok_or(BasicAuthError::MissingColon)?;
+    validate_text(user_id)?;
+    validate_text(password)?;
+    Ok(Credentials {
+        user_id: user_id.to_string(),
+        password: password.to_string(),
+    })
+}
+
+/// The value for a `WWW-Authenticate` header announcing UTF-8 credentials.
+pub fn challenge(realm: &str) -> String {
+    let realm = realm.replace('\\', "\\\\").replace('"', "\\\"");
+    format!("{SCHEME} realm=\"{realm}\", charset=\"UTF-8\"")
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,124 @@
+use super::*;
+
+#[test]
+fn test_rfc7617_example() {
+    let credentials =
+        parse_header_value("Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==").expect("valid header");
+    assert_eq!(credentials.user_id, "Aladdin");
+    assert_eq!(credentials.password, "open sesame");
+    assert_eq!(
+        credentials.header_value(),
+        "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ=="
+    );
+}
+
+#[test]
+fn test_utf8_credentials() {
+    // RFC 7617 section 2.1: "test" / "123£" encoded as UTF-8
+    let credentials = parse_header_value("Basic dGVzdDoxMjPCow==").expect("valid header");
+    assert_eq!(credentials.user_id, "test");
+    assert_eq!(credentials.password, "123£");
+    let roundtrip = Credentials::new("test", "123£").expect("valid credentials");
+    assert_eq!(roundtrip.header_value(), "Basic dGVzdDoxMjPCow==");
+}
+
+#[test]
+fn test_splits_on_first_colon_only() {
+    let header = Credentials::new("user", "pa:ss:word")
+        .unwrap()
+        .header_value();
+    let credentials = parse_header_value(&header).expect("valid header");
+    assert_eq!(credentials.user_id, "user");
+    assert_eq!(credentials.password, "pa:ss:word");
+
+    let credentials = parse_header_value("Basic OnNlY3JldA==").expect("empty user-id is allowed");
+    assert_eq!(credentials.user_id, "");
+    assert_eq!(credentials.password, "secret");
+}
+
+#[test]
+fn test_scheme_is_case_insensitive_and_whitespace_tolerant() {
+    let credentials = parse_header_value("  basic   QWxhZGRpbjpvcGVuIHNlc2FtZQ==  ").unwrap();
+    assert_eq!(credentials.user_id, "Aladdin");
+}
+
+#[test]
+fn test_parse_errors() {
+    match parse_header_value("Bearer abc") {
+        Err(BasicAuthError::WrongScheme(scheme)) if scheme == "Bearer" => {}
+        other => panic!("expected WrongScheme, got {:?}", other),
+    }
+    assert!(matches!(
+        parse_header_value("Basic"),
+        Err(BasicAuthError::MissingCredentials)
+    ));
+    assert!(matches!(
+        parse_header_value("Basic   "),
+        Err(BasicAuthError::MissingCredentials)
+    ));
+    assert!(matches!(
+        parse_header_value("Basic QWxh!"),
+        Err(BasicAuthError::InvalidBase64(
+            Base64Error::InvalidCharacter(b'!')
+        ))
+    ));
+    // "test:123" followed by a lone Latin-1 '£' byte
+    assert!(matches!(
+        parse_header_value("Basic dGVzdDoxMjOj"),
+        Err(BasicAuthError::InvalidUtf8)
+    ));
+    // "Aladdin"
+    assert!(matches!(
+        parse_header_value("Basic QWxhZGRpbg=="),
+        Err(BasicAuthError::MissingColon)
+    ));
+    // "a:b\n"
+    assert!(matches!(
+        parse_header_value("Basic YTpiCg=="),
+        Err(BasicAuthError::ControlCharacter)
+    ));
+}
+
+#[test]
+fn test_new_validation() {
+    assert!(matches!(
+        Credentials::new("a:b", "c"),
+        Err(BasicAuthError::UserIdContainsColon)
+    ));
+    assert!(matches!(
+        Credentials::new("a", "b\r\n"),
+        Err(BasicAuthError::ControlCharacter)
+    ));
+}
+
+#[test]
+fn test_debug_redacts_password() {
+    let credentials = Credentials::new("Aladdin", "open sesame").unwrap();
+    let debug = format!("{credentials:?}");
+    assert!(debug.contains("Aladdin"));
+    assert!(!debug.contains("sesame"));
+}
+
+#[test]
+fn test_challenge() {
+    assert_eq!(
+        challenge("WallyWorld"),
+        "Basic realm=\"WallyWorld\", charset=\"UTF-8\""
+    );
+    assert_eq!(
+        challenge("say \"hi\""),
+        "Basic realm=\"say \\\"hi\\\"\", charset=\"UTF-8\""
+    );
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", BasicAuthError::WrongScheme("Bearer".into())).contains("Bearer"));
+    assert!(format!("{}", BasicAuthError::MissingCredentials).contains("No credentials"));
+    let e = BasicAuthError::InvalidBase64(Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("Invalid character"));
+    assert!(format!("{}", BasicAuthError::InvalidUtf8).contains("UTF-8"));
+    assert!(format!("{}", BasicAuthError::MissingColon).contains("':'"));
+    assert!(format!("{}", BasicAuthError::ControlCharacter).contains("control"));
+    assert!(format!("{}", BasicAuthError::UserIdContainsColon).contains("User-id"));
+}
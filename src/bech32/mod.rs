@@ -0,0 +1,262 @@
+use std::error;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const SEPARATOR: u8 = b'1';
+const CHECKSUM_LENGTH: usize = 6;
+const MAX_LENGTH: usize = 90;
+const MAX_HRP_LENGTH: usize = 83;
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bech32Variant {
+    /// BIP-173, used for segwit v0 addresses
+    Bech32,
+    /// BIP-350, used for segwit v1+ (taproot) addresses
+    Bech32m,
+}
+
+pub struct Bech32 {
+    /// Human-readable part, lowercased
+    pub hrp: String,
+    /// 5-bit values between the separator and the checksum
+    pub data: Vec<u8>,
+    pub variant: Bech32Variant,
+}
+
+#[derive(Debug)]
+pub enum Bech32Error {
+    InvalidCharacter(u8),
+    MixedCase,
+    MissingSeparator,
+    InvalidHrpLength(usize),
+    InvalidLength(usize),
+    ChecksumTooShort(usize),
+    InvalidChecksum,
+    InvalidQuintet(u8),
+    InvalidPadding,
+}
+
+impl error::Error for Bech32Error {}
+
+impl std::fmt::Display for Bech32Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Bech32Error::InvalidCharacter(c) => {
+                write!(f, "Invalid character \'{}\'", c.escape_ascii())
+            }
+            Bech32Error::MixedCase => write!(f, "Mixed upper and lower case"),
+            Bech32Error::MissingSeparator => write!(f, "Missing separator \'1\'"),
+            Bech32Error::InvalidHrpLength(length) => {
+                write!(
+                    f,
+                    "Human-readable part length {} not within 1..={}",
+                    *length, MAX_HRP_LENGTH
+                )
+            }
+            Bech32Error::InvalidLength(length) => {
+                write!(f, "Length {} exceeds {} characters", *length, MAX_LENGTH)
+            }
+            Bech32Error::ChecksumTooShort(length) => {
+                write!(
+                    f,
+                    "Data part of {} characters is shorter than the checksum",
+                    *length
+                )
+            }
+            Bech32Error::InvalidChecksum => write!(f, "Invalid checksum"),
+            Bech32Error::InvalidQuintet(value) => {
+                write!(f, "Value {} does not fit into 5 bits", *value)
+            }
+            Bech32Error::InvalidPadding => {
+                write!(f, "Padding is longer than 4 bits or not all zeroes")
+            }
+        }
+    }
+}
+
+impl Bech32Variant {
+    fn checksum_constant(&self) -> u32 {
+        match self {
+            Bech32Variant::Bech32 => 1,
+            Bech32Variant::Bech32m => 0x2bc830a3,
+        }
+    }
+}
+
+impl Bech32 {
+    /// Regroups the 5-bit data back into bytes, as for a witness program.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Bech32Error> {
+        from_quintets(&self.data)
+    }
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    values.fold(1u32, |checksum, value| {
+        let top = checksum >> 25;
+        let checksum = ((checksum & 0x1ffffff) << 5) ^ value as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(checksum, |checksum, (_, g)| checksum ^ g)
+    })
+}
+
+fn hrp_expand(hrp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    hrp.iter()
+        .map(|c| c >> 5)
+        .chain([0])
+        .chain(hrp.iter().map(|c| c & 31))
+}
+
+fn create_checksum(variant: Bech32Variant, hrp: &[u8], data: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let values = hrp_expand(hrp)
+        .chain(data.iter().copied())
+        .chain([0u8; CHECKSUM_LENGTH]);
+    let checksum = polymod(values) ^ variant.checksum_constant();
+    let mut quintets = [0u8; CHECKSUM_LENGTH];
+    for (i, quintet) in quintets.iter_mut().enumerate() {
+        *quintet = ((checksum >> (5 * (CHECKSUM_LENGTH - 1 - i))) & 31) as u8;
+    }
+    quintets
+}
+
+fn unpack_quintets(raw_group: &[u8]) -> [u8; 8] {
+    let bits_per_element: usize = 5;
+    let mut padded_group = [0u8; 8];
+    padded_group[3..3 + raw_group.len()].copy_from_slice(raw_group);
+    let number = u64::from_be_bytes(padded_group);
+    let element_mask = (1u64 << bits_per_element) - 1;
+    let mut quintets = [0u8; 8];
+    for (i, quintet) in quintets.iter_mut().enumerate() {
+        *quintet = ((number >> (bits_per_element * (7 - i))) & element_mask) as u8;
+    }
+    quintets
+}
+
+fn pack_quintets(encoded_group: &[u8]) -> [u8; 5] {
+    let bits_per_element: usize = 5;
+    let number = encoded_group
+        .iter()
+        .chain(std::iter::repeat(&0))
+        .take(8)
+        .fold(0u64, |number, quintet| {
+            (number << bits_per_element) | *quintet as u64
+        });
+    let bytes = number.to_be_bytes();
+    [bytes[3], bytes[4], bytes[5], bytes[6], bytes[7]]
+}
+
+/// Regroups bytes into 5-bit values, zero-padding the last one.
+pub fn to_quintets(bytes: &[u8]) -> Vec<u8> {
+    let length = (bytes.len() * 8).div_ceil(5);
+    bytes
+        .chunks(5)
+        .flat_map(unpack_quintets)
+        .take(length)
+        .collect()
+}
+
+/// Regroups 5-bit values into bytes. Leftover bits must be fewer than 5
+/// and all zero, so every byte string has exactly one encoding.
+pub fn from_quintets(quintets: &[u8]) -> Result<Vec<u8>, Bech32Error> {
+    if let Some(q) = quintets.iter().find(|q| **q > 31) {
+        return Err(Bech32Error::InvalidQuintet(*q));
+    }
+    let length = quintets.len() * 5 / 8;
+    let padding_bits = quintets.len() * 5 % 8;
+    let last_mask = (1u8 << padding_bits) - 1;
+    if padding_bits >= 5 || quintets.last().is_some_and(|q| q & last_mask != 0) {
+        return Err(Bech32Error::InvalidPadding);
+    }
+    Ok(quintets
+        .chunks(8)
+        .flat_map(pack_quintets)
+        .take(length)
+        .collect())
+}
+
+fn validate_case_and_characters(bytes: &[u8]) -> Result<(), Bech32Error> {
+    if let Some(c) = bytes.iter().find(|c| !(33..=126).contains(*c)) {
+        return Err(Bech32Error::InvalidCharacter(*c));
+    }
+    if bytes.iter().any(u8::is_ascii_lowercase) && bytes.iter().any(u8::is_ascii_uppercase) {
+        return Err(Bech32Error::MixedCase);
+    }
+    Ok(())
+}
+
+/// Encodes 5-bit `data` under `hrp` with the checksum of `variant`.
+pub fn encode(variant: Bech32Variant, hrp: &str, data: &[u8]) -> Result<String, Bech32Error> {
+    validate_case_and_characters(hrp.as_bytes())?;
+    if hrp.is_empty() || hrp.len() > MAX_HRP_LENGTH {
+        return Err(Bech32Error::InvalidHrpLength(hrp.len()));
+    }
+    if let Some(q) = data.iter().find(|q| **q > 31) {
+        return Err(Bech32Error::InvalidQuintet(*q));
+    }
+    let length = hrp.len() + 1 + data.len() + CHECKSUM_LENGTH;
+    if length > MAX_LENGTH {
+        return Err(Bech32Error::InvalidLength(length));
+    }
+    let hrp = hrp.to_ascii_lowercase();
+    let checksum = create_checksum(variant, hrp.as_bytes(), data);
+    let symbols = data
+        .iter()
+        .chain(&checksum)
+        .map(|q| CHARSET[*q as usize] as char);
+    Ok(hrp.chars().chain(['1']).chain(symbols).collect())
+}
+
+/// Encodes bytes, regrouped into 5-bit values, under `hrp`.
+pub fn encode_bytes(
+    variant: Bech32Variant,
+    hrp: &str,
+    bytes: &[u8],
+) -> Result<String, Bech32Error> {
+    encode(variant, hrp, &to_quintets(bytes))
+}
+
+/// Decodes a Bech32 or Bech32m string, telling the two apart by which
+/// checksum constant verifies.
+pub fn decode(bech32_encoded: &str) -> Result<Bech32, Bech32Error> {
+    let bytes = bech32_encoded.as_bytes();
+    if bytes.len() > MAX_LENGTH {
+        return Err(Bech32Error::InvalidLength(bytes.len()));
+    }
+    validate_case_and_characters(bytes)?;
+    let lowercase = bytes.to_ascii_lowercase();
+    let separator = lowercase
+        .iter()
+        .rposition(|c| *c == SEPARATOR)
+        .ok_or(Bech32Error::MissingSeparator)?;
+    let (hrp, data_part) = (&lowercase[..separator], &lowercase[separator + 1..]);
+    if hrp.is_empty() || hrp.len() > MAX_HRP_LENGTH {
+        return Err(Bech32Error::InvalidHrpLength(hrp.len()));
+    }
+    if data_part.len() < CHECKSUM_LENGTH {
+        return Err(Bech32Error::ChecksumTooShort(data_part.len()));
+    }
+    let values = data_part
+        .iter()
+        .map(|c| match CHARSET.iter().position(|s| s == c) {
+            Some(value) => Ok(value as u8),
+            None => Err(Bech32Error::InvalidCharacter(*c)),
+        })
+        .collect::<Result<Vec<u8>, Bech32Error>>()?;
+    let residue = polymod(hrp_expand(hrp).chain(values.iter().copied()));
+    let variant = [Bech32Variant::Bech32, Bech32Variant::Bech32m]
+        .into_iter()
+        .find(|variant| variant.checksum_constant() == residue)
+        .ok_or(Bech32Error::InvalidChecksum)?;
+    let mut data = values;
+    data.truncate(data.len() - CHECKSUM_LENGTH);
+    Ok(Bech32 {
+        hrp: String::from_utf8_lossy(hrp).into_owned(),
+        data,
+        variant,
+    })
+}
+
+#[cfg(test)]
+mod tests;
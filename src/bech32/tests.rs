@@ -0,0 +1,217 @@
+use super::*;
+
+const BIP173_VALID: [&str; 6] = [
+    "A12UEL5L",
+    "a12uel5l",
+    "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+    "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+    "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+    "?1ezyfcl",
+];
+
+const BIP350_VALID: [&str; 6] = [
+    "A1LQFN3A",
+    "a1lqfn3a",
+    "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+    "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+    "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+    "?1v759aa",
+];
+
+#[test]
+fn test_bip173_valid_vectors() {
+    for vector in BIP173_VALID {
+        let decoded = decode(vector).unwrap_or_else(|e| panic!("{vector}: {e}"));
+        assert_eq!(decoded.variant, Bech32Variant::Bech32, "{vector}");
+        let encoded = encode(Bech32Variant::Bech32, &decoded.hrp, &decoded.data).unwrap();
+        assert_eq!(encoded, vector.to_ascii_lowercase());
+    }
+}
+
+#[test]
+fn test_bip350_valid_vectors() {
+    for vector in BIP350_VALID {
+        let decoded = decode(vector).unwrap_or_else(|e| panic!("{vector}: {e}"));
+        assert_eq!(decoded.variant, Bech32Variant::Bech32m, "{vector}");
+        let encoded = encode(Bech32Variant::Bech32m, &decoded.hrp, &decoded.data).unwrap();
+        assert_eq!(encoded, vector.to_ascii_lowercase());
+    }
+}
+
+type ErrorCheck = fn(&Bech32Error) -> bool;
+
+#[test]
+fn test_invalid_vectors() {
+    let cases: [(&str, ErrorCheck); 19] = [
+        // BIP-173
+        ("\x201nwldj5", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(0x20))
+        }),
+        ("\x7f1axkwrx", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(0x7f))
+        }),
+        ("\u{80}1eym55h", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(0xc2))
+        }),
+        (
+            "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+            |e| matches!(e, Bech32Error::InvalidLength(91)),
+        ),
+        ("pzry9x0s0muk", |e| {
+            matches!(e, Bech32Error::MissingSeparator)
+        }),
+        ("1pzry9x0s0muk", |e| {
+            matches!(e, Bech32Error::InvalidHrpLength(0))
+        }),
+        ("x1b4n0q5v", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(b'b'))
+        }),
+        ("li1dgmt3", |e| {
+            matches!(e, Bech32Error::ChecksumTooShort(5))
+        }),
+        ("de1lg7wt\u{ff}", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(0xc3))
+        }),
+        ("A1G7SGD8", |e| matches!(e, Bech32Error::InvalidChecksum)),
+        ("10a06t8", |e| matches!(e, Bech32Error::InvalidHrpLength(0))),
+        ("1qzzfhee", |e| {
+            matches!(e, Bech32Error::InvalidHrpLength(0))
+        }),
+        // BIP-350
+        ("qyrz8wqd2c9m", |e| {
+            matches!(e, Bech32Error::MissingSeparator)
+        }),
+        ("y1b0jsk6g", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(b'b'))
+        }),
+        ("lt1igcx5c0", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(b'i'))
+        }),
+        ("in1muywd", |e| {
+            matches!(e, Bech32Error::ChecksumTooShort(5))
+        }),
+        ("mm1crxm3i", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(b'i'))
+        }),
+        ("au1s5cgom", |e| {
+            matches!(e, Bech32Error::InvalidCharacter(b'o'))
+        }),
+        ("M1VUXWEZ", |e| matches!(e, Bech32Error::InvalidChecksum)),
+    ];
+    for (vector, expected) in cases {
+        match decode(vector) {
+            Err(e) if expected(&e) => {}
+            Err(e) => panic!("{vector:?}: unexpected error {e:?}"),
+            Ok(_) => panic!("{vector:?}: expected Err, got Ok"),
+        }
+    }
+}
+
+#[test]
+fn test_mixed_case_is_rejected() {
+    match decode("a12UEL5L") {
+        Err(Bech32Error::MixedCase) => {}
+        other => panic!("expected MixedCase, got {:?}", other.err()),
+    }
+    match encode(Bech32Variant::Bech32, "Bc", &[0]) {
+        Err(Bech32Error::MixedCase) => {}
+        other => panic!("expected MixedCase, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_segwit_addresses() {
+    // BIP-173 P2WPKH and BIP-350 v1 witness programs
+    let v0 = decode("BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4").unwrap();
+    assert_eq!(v0.hrp, "bc");
+    assert_eq!(v0.variant, Bech32Variant::Bech32);
+    assert_eq!(v0.data[0], 0);
+    let program = from_quintets(&v0.data[1..]).unwrap();
+    assert_eq!(
+        program,
+        [
+            0x75, 0x1e, 0x76, 0xe8, 0x19, 0x91, 0x96, 0xd4, 0x54, 0x94, 0x1c, 0x45, 0xd1, 0xb3,
+            0xa3, 0x23, 0xf1, 0x43, 0x3b, 0xd6
+        ]
+    );
+
+    let v1 = decode("bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y")
+        .unwrap();
+    assert_eq!(v1.variant, Bech32Variant::Bech32m);
+    assert_eq!(v1.data[0], 1);
+    assert_eq!(
+        from_quintets(&v1.data[1..]).unwrap(),
+        [program.clone(), program].concat()
+    );
+}
+
+#[test]
+fn test_encode_bytes_roundtrip() {
+    for length in 0..=12 {
+        let bytes: Vec<u8> = (0..length).map(|i| (i * 37 + 11) as u8).collect();
+        let encoded = encode_bytes(Bech32Variant::Bech32m, "test", &bytes).unwrap();
+        let decoded = decode(&encoded).expect("decode should succeed");
+        assert_eq!(decoded.variant, Bech32Variant::Bech32m);
+        assert_eq!(decoded.to_bytes().unwrap(), bytes);
+    }
+}
+
+#[test]
+fn test_quintet_regrouping() {
+    assert_eq!(to_quintets(&[0xff]), [31, 28]);
+    assert_eq!(
+        to_quintets(&[0x00, 0x01, 0x02, 0x03, 0x04]),
+        [0, 0, 0, 16, 4, 0, 24, 4]
+    );
+    assert_eq!(
+        from_quintets(&[0, 0, 0, 16, 4, 0, 24, 4]).unwrap(),
+        [0, 1, 2, 3, 4]
+    );
+    // 5 leftover bits are a whole extra quintet, not padding
+    assert!(matches!(
+        from_quintets(&[31]),
+        Err(Bech32Error::InvalidPadding)
+    ));
+    // non-zero padding bits
+    assert!(matches!(
+        from_quintets(&[31, 29]),
+        Err(Bech32Error::InvalidPadding)
+    ));
+    assert!(matches!(
+        from_quintets(&[32, 0]),
+        Err(Bech32Error::InvalidQuintet(32))
+    ));
+}
+
+#[test]
+fn test_encode_errors() {
+    assert!(matches!(
+        encode(Bech32Variant::Bech32, "", &[]),
+        Err(Bech32Error::InvalidHrpLength(0))
+    ));
+    assert!(matches!(
+        encode(Bech32Variant::Bech32, "bc", &[32]),
+        Err(Bech32Error::InvalidQuintet(32))
+    ));
+    assert!(matches!(
+        encode(Bech32Variant::Bech32, "bc", &[0; 82]),
+        Err(Bech32Error::InvalidLength(91))
+    ));
+    assert!(matches!(
+        encode(Bech32Variant::Bech32, "b c", &[]),
+        Err(Bech32Error::InvalidCharacter(b' '))
+    ));
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", Bech32Error::InvalidCharacter(0x7f)).contains("\\x7f"));
+    assert!(format!("{}", Bech32Error::MixedCase).contains("Mixed"));
+    assert!(format!("{}", Bech32Error::MissingSeparator).contains("separator"));
+    assert!(format!("{}", Bech32Error::InvalidHrpLength(0)).contains("length 0"));
+    assert!(format!("{}", Bech32Error::InvalidLength(91)).contains("Length 91"));
+    assert!(format!("{}", Bech32Error::ChecksumTooShort(5)).contains("5 characters"));
+    assert!(format!("{}", Bech32Error::InvalidChecksum).contains("checksum"));
+    assert!(format!("{}", Bech32Error::InvalidQuintet(32)).contains("32"));
+    assert!(format!("{}", Bech32Error::InvalidPadding).contains("Padding"));
+}
@@ -0,0 +1,173 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use std::error;
+
+const SCHEME: &str = "data:";
+const BASE64_MARKER: &str = ";base64";
+const DEFAULT_MIME_TYPE: &str = "text/plain";
+const DEFAULT_CHARSET: &str = "US-ASCII";
+
+pub struct DataUrl {
+    pub mime_type: String,
+    /// Media type parameters other than the `;base64` marker, e.g. `charset`
+    pub parameters: Vec<(String, String)>,
+    pub is_base64: bool,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum DataUrlError {
+    MissingScheme,
+    MissingComma,
+    InvalidPercentEncoding(usize),
+    InvalidBase64(Base64Error),
+}
+
+impl error::Error for DataUrlError {}
+
+impl std::fmt::Display for DataUrlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataUrlError::MissingScheme => write!(f, "URL does not start with \"data:\""),
+            DataUrlError::MissingComma => {
+                write!(f, "Missing \',\' between media type and data")
+            }
+            DataUrlError::InvalidPercentEncoding(position) => {
+                write!(f, "Invalid percent-encoding at position {}", *position)
+            }
+            DataUrlError::InvalidBase64(e) => write!(f, "Invalid base64 payload: {}", e),
+        }
+    }
+}
+
+impl DataUrl {
+    /// The `charset` parameter, defaulting to US-ASCII for `text/*` types per RFC 2397.
+    pub fn charset(&self) -> Option<&str> {
+        match self.parameters.iter().find(|(k, _)| k == "charset") {
+            Some((_, charset)) => Some(charset),
+            None if self.mime_type.starts_with("text/") => Some(DEFAULT_CHARSET),
+            None => None,
+        }
+    }
+}
+
+fn percent_decode(encoded: &str) -> Result<Vec<u8>, DataUrlError> {
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(DataUrlError::InvalidPercentEncoding(i))?;
+            decoded.push(hex);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(decoded)
+}
+
+fn parse_media_type(media_type: &str) -> (String, Vec<(String, String)>) {
+    let mut parts = media_type.split(';').map(str::trim);
+    let mime_type = parts.next().unwrap_or_default().to_ascii_lowercase();
+    let parameters = parts
+        .filter_map(|parameter| parameter.split_once('='))
+        .map(|(key, value)| {
+            (
+                key.to_ascii_lowercase(),
+                value.trim_matches('"').to_string(),
+            )
+        })
+        .collect();
+    (mime_type, parameters)
+}
+
+/// Builds `data:<mime_type>;base64,<payload>` with the standard alphabet.
+pub fn encode(mime_type: &str, data: &[u8]) -> String {
+    let payload = encode_to_vec(&Base64Config::standard(), data);
+    // the standard alphabet and '=' are all URL-safe ASCII
+    let payload = String::from_utf8(payload).unwrap();
+    format!("{SCHEME}{mime_type}{BASE64_MARKER},{payload}")
+}
+
+pub fn parse(url: &str) -> Result<DataUrl, DataUrlError> {
+    let url = url.trim();
+    let rest = match url.get(..SCHEME.len()) {
+        Some(scheme) if scheme.eq_ignore_ascii_case(SCHEME) => &url[SCHEME.len()..],
+        _ => return Err(DataUrlError::MissingScheme),
+    };
+    let (media_type, payload) = rest.split_once(',').ok_or(DataUrlError::MissingComma)?;
+    // `get` rather than indexing, as the split can fall inside a multi-byte character
+    let split = media_type.len().saturating_sub(BASE64_MARKER.len());
+    let (media_type, is_base64) = match media_type.get(split..) {
+        Some(marker) if marker.eq_ignore_ascii_case(BASE64_MARKER) => (&media_type[..split], true),
+        _ => (media_type, false),
+    };
+    let (mut mime_type, parameters) = parse_media_type(media_type);
+    if mime_type.is_empty() {
+        mime_type = DEFAULT_MIME_TYPE.to_string();
+    }
+    let mut data = percent_decode(payload)?;
+    if is_base64 {
+        data.retain(|b| !b.is_ascii_whitespace());
+        data =
+            decode_to_vec(&Base64Config::standard(), &data).map_err(DataUrlError::InvalidBase64)?;
+    }
+    Ok(DataUrl {
+        mime_type,
+        parameters,
+        is_base64,
+        data,
+    })
+}
+
+/// Guesses a MIME type from well-known file signatures.
+pub fn sniff_mime_type(bytes: &[u8]) -> &'static str {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"BM", "image/bmp"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"OggS", "audio/ogg"),
+        (b"ID3", "audio/mpeg"),
+        (b"\x00asm", "application/wasm"),
+    ];
+    if let Some((_, mime_type)) = SIGNATURES
+        .iter()
+        .find(|(magic, _)| bytes.starts_with(magic))
+    {
+        return mime_type;
+    }
+    // RIFF containers carry their format four bytes after the chunk size
+    match (bytes.get(..4), bytes.get(8..12)) {
+        (Some(b"RIFF"), Some(b"WEBP")) => return "image/webp",
+        (Some(b"RIFF"), Some(b"WAVE")) => return "audio/wav",
+        _ => {}
+    }
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return "application/octet-stream";
+    };
+    let start = text.trim_start().get(..64).unwrap_or(text.trim_start());
+    let start = start.to_ascii_lowercase();
+    if start.starts_with("<svg") || (start.starts_with("<?xml") && text.contains("<svg")) {
+        "image/svg+xml"
+    } else if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        "text/html"
+    } else if text.is_ascii() {
+        "text/plain"
+    } else {
+        "text/plain;charset=utf-8"
+    }
+}
+
+#[cfg(test)]
+mod tests;
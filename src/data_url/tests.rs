@@ -0,0 +1,137 @@
+use super::*;
+
+const PNG_1X1: &[u8] = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01\x00\x00\x00\x01";
+
+#[test]
+fn test_encode() {
+    assert_eq!(
+        encode("text/plain", b"Hello, World!"),
+        "data:text/plain;base64,SGVsbG8sIFdvcmxkIQ=="
+    );
+    assert_eq!(
+        encode("application/octet-stream", b""),
+        "data:application/octet-stream;base64,"
+    );
+}
+
+#[test]
+fn test_parse_base64_roundtrip() {
+    let url = encode("image/png", PNG_1X1);
+    let parsed = parse(&url).expect("parse should succeed");
+    assert_eq!(parsed.mime_type, "image/png");
+    assert!(parsed.is_base64);
+    assert!(parsed.parameters.is_empty());
+    assert_eq!(parsed.charset(), None);
+    assert_eq!(parsed.data, PNG_1X1);
+}
+
+#[test]
+fn test_parse_percent_encoded() {
+    let parsed = parse("data:,A%20brief%20note").expect("parse should succeed");
+    assert!(!parsed.is_base64);
+    assert_eq!(parsed.mime_type, "text/plain");
+    assert_eq!(parsed.charset(), Some("US-ASCII"));
+    assert_eq!(parsed.data, b"A brief note");
+}
+
+#[test]
+fn test_parse_charset_parameter() {
+    let parsed =
+        parse("data:text/plain;charset=UTF-8;base64,SGVsbG8g4piD").expect("parse should succeed");
+    assert_eq!(parsed.charset(), Some("UTF-8"));
+    assert_eq!(parsed.data, "Hello ☃".as_bytes());
+
+    let parsed = parse("data:;charset=iso-8859-7,%be%fg%be").map(|p| p.data);
+    assert!(matches!(
+        parsed,
+        Err(DataUrlError::InvalidPercentEncoding(3))
+    ));
+
+    let parsed = parse("data:;charset=iso-8859-7,%be%d3%be").expect("parse should succeed");
+    assert_eq!(parsed.mime_type, "text/plain");
+    assert_eq!(parsed.charset(), Some("iso-8859-7"));
+    assert_eq!(parsed.data, [0xBE, 0xD3, 0xBE]);
+}
+
+#[test]
+fn test_parse_is_case_insensitive_and_tolerates_whitespace() {
+    let parsed = parse(" DATA:Image/PNG;BASE64,iVBO Rw0K\nGgo= ").expect("parse should succeed");
+    assert_eq!(parsed.mime_type, "image/png");
+    assert_eq!(parsed.data, &PNG_1X1[..8]);
+    // percent-encoded base64 as produced by some URL serializers
+    let parsed = parse("data:;base64,SGk%3D").expect("parse should succeed");
+    assert_eq!(parsed.data, b"Hi");
+}
+
+#[test]
+fn test_parse_non_ascii_media_type() {
+    // the marker-sized tail starts inside the two-byte 'é'
+    let parsed = parse("data:é123456,x").expect("parse should succeed");
+    assert!(!parsed.is_base64);
+    assert_eq!(parsed.mime_type, "é123456");
+    assert_eq!(parsed.data, b"x");
+    let parsed = parse("data:text/é;base64,SGk=").expect("parse should succeed");
+    assert!(parsed.is_base64);
+    assert_eq!(parsed.data, b"Hi");
+}
+
+#[test]
+fn test_parse_errors() {
+    assert!(matches!(
+        parse("http://example.com"),
+        Err(DataUrlError::MissingScheme)
+    ));
+    assert!(matches!(
+        parse("data:text/plain"),
+        Err(DataUrlError::MissingComma)
+    ));
+    assert!(matches!(
+        parse("data:,%4"),
+        Err(DataUrlError::InvalidPercentEncoding(0))
+    ));
+    match parse("data:;base64,SGk!") {
+        Err(DataUrlError::InvalidBase64(Base64Error::InvalidCharacter(b'!'))) => {}
+        other => panic!("expected InvalidBase64, got {:?}", other.map(|p| p.data)),
+    }
+}
+
+#[test]
+fn test_sniff_mime_type() {
+    assert_eq!(sniff_mime_type(PNG_1X1), "image/png");
+    assert_eq!(
+        sniff_mime_type(b"\xff\xd8\xff\xe0\x00\x10JFIF"),
+        "image/jpeg"
+    );
+    assert_eq!(sniff_mime_type(b"GIF89a\x01\x00"), "image/gif");
+    assert_eq!(
+        sniff_mime_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "),
+        "image/webp"
+    );
+    assert_eq!(sniff_mime_type(b"%PDF-1.7\n"), "application/pdf");
+    assert_eq!(
+        sniff_mime_type(b"  <svg xmlns=\"http://www.w3.org/2000/svg\"/>"),
+        "image/svg+xml"
+    );
+    assert_eq!(
+        sniff_mime_type(b"<!DOCTYPE html><html></html>"),
+        "text/html"
+    );
+    assert_eq!(sniff_mime_type(b"plain words"), "text/plain");
+    assert_eq!(
+        sniff_mime_type("Grüße".as_bytes()),
+        "text/plain;charset=utf-8"
+    );
+    assert_eq!(
+        sniff_mime_type(b"\xfe\xed\xfa\xce"),
+        "application/octet-stream"
+    );
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", DataUrlError::MissingScheme).contains("data:"));
+    assert!(format!("{}", DataUrlError::MissingComma).contains("\',\'"));
+    assert!(format!("{}", DataUrlError::InvalidPercentEncoding(3)).contains("position 3"));
+    let e = DataUrlError::InvalidBase64(Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("Invalid character"));
+}
@@ -0,0 +1,96 @@
+use super::*;
+
+// the HS256 example token from jwt.io, signed with "your-256-bit-secret"
+const HS256_TOKEN: &str = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+    eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ.\
+    SflKxwRJSMeKKF2QT4fwpMeJf36POk6yJV_adQssw5c";
+
+#[test]
+fn test_parse_hs256_token() {
+    let jwt = parse(HS256_TOKEN).expect("parse should succeed");
+    assert_eq!(jwt.algorithm(), Some("HS256"));
+    assert_eq!(jwt.header["typ"], "JWT");
+    let payload = jwt.payload_json().expect("payload is JSON");
+    assert_eq!(payload["name"], "John Doe");
+    assert_eq!(payload["iat"], 1516239022);
+    assert_eq!(jwt.signature.len(), 32);
+    assert_eq!(jwt.signature[..4], [0x49, 0xF9, 0x4A, 0xC7]);
+    assert!(!jwt.is_unsecured());
+}
+
+#[test]
+fn test_verify_hs256() {
+    let jwt = parse(HS256_TOKEN).expect("parse should succeed");
+    assert!(
+        jwt.verify_hs256(b"your-256-bit-secret")
+            .expect("HS256 is supported")
+    );
+    assert!(
+        !jwt.verify_hs256(b"wrong secret")
+            .expect("HS256 is supported")
+    );
+
+    // tampering with the payload invalidates the signature
+    let tampered = HS256_TOKEN.replace("eyJzdWIiOiIxMjM0", "eyJzdWIiOiIxMjM1");
+    let jwt = parse(&tampered).expect("parse should succeed");
+    assert!(
+        !jwt.verify_hs256(b"your-256-bit-secret")
+            .expect("HS256 is supported")
+    );
+}
+
+#[test]
+fn test_alg_none_is_flagged() {
+    // {"alg":"none"}.{"sub":"admin"}.
+    let jwt = parse("eyJhbGciOiJub25lIn0.eyJzdWIiOiJhZG1pbiJ9.").expect("parse should succeed");
+    assert!(jwt.is_unsecured());
+    assert!(jwt.signature.is_empty());
+    match jwt.verify_hs256(b"secret") {
+        Err(JwtError::UnsupportedAlgorithm(alg)) if alg == "none" => {}
+        other => panic!("expected UnsupportedAlgorithm, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_non_json_payload() {
+    // {"alg":"HS256"}.hello.
+    let jwt = parse("eyJhbGciOiJIUzI1NiJ9.aGVsbG8.").expect("parse should succeed");
+    assert!(jwt.payload_json().is_none());
+    assert_eq!(jwt.payload, b"hello");
+}
+
+#[test]
+fn test_parse_errors() {
+    match parse("a.b") {
+        Err(JwtError::WrongSegmentCount(2)) => {}
+        other => panic!(
+            "expected WrongSegmentCount(2), got {:?}",
+            other.map(|j| j.header)
+        ),
+    }
+    match parse("eyJhbGciOiJub25lIn0.e+J9.") {
+        Err(JwtError::InvalidBase64("payload", Base64Error::InvalidCharacter(b'+'))) => {}
+        other => panic!("expected InvalidBase64, got {:?}", other.map(|j| j.header)),
+    }
+    match parse("aGVsbG8.e30.") {
+        Err(JwtError::InvalidHeader(_)) => {}
+        other => panic!("expected InvalidHeader, got {:?}", other.map(|j| j.header)),
+    }
+    // valid JSON, but not an object: `[]` and `1`
+    for header in ["W10", "MQ"] {
+        match parse(&format!("{header}.e30.")) {
+            Err(JwtError::InvalidHeader(_)) => {}
+            other => panic!("expected InvalidHeader, got {:?}", other.map(|j| j.header)),
+        }
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", JwtError::WrongSegmentCount(2)).contains("found 2"));
+    let e = JwtError::InvalidBase64("payload", Base64Error::InvalidCharacter(b'+'));
+    assert!(format!("{e}").contains("payload"));
+    let e = JwtError::InvalidHeader(serde_json::from_str::<serde_json::Value>("{").unwrap_err());
+    assert!(format!("{e}").contains("JSON"));
+    assert!(format!("{}", JwtError::UnsupportedAlgorithm("RS256".into())).contains("RS256"));
+}
@@ -0,0 +1,36 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "formats")]
+pub mod armor;
+#[cfg(feature = "formats")]
+pub mod base45;
+pub mod base64;
+#[cfg(feature = "formats")]
+pub mod base85;
+#[cfg(feature = "formats")]
+pub mod base91;
+#[cfg(feature = "formats")]
+pub mod basic_auth;
+#[cfg(feature = "formats")]
+pub mod bech32;
+#[cfg(feature = "formats")]
+pub mod data_url;
+#[cfg(feature = "jwt")]
+pub mod jwt;
+#[cfg(feature = "formats")]
+pub mod mime;
+#[cfg(feature = "formats")]
+pub mod multibase;
+#[cfg(feature = "formats")]
+pub mod pem;
+#[cfg(feature = "formats")]
+pub mod radix;
+#[cfg(feature = "ssh")]
+pub mod ssh;
+#[cfg(feature = "formats")]
+pub mod uuencode;
+#[cfg(feature = "formats")]
+pub mod yenc;
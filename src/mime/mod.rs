@@ -0,0 +1,304 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use std::error;
+use std::path::Path;
+
+const BODY_LINE_WIDTH: usize = 76;
+const MAX_ENCODED_WORD_LENGTH: usize = 75;
+const ENCODED_WORD_CHARSET: &str = "UTF-8";
+
+pub struct MimePart {
+    pub content_type: String,
+    pub filename: Option<String>,
+    pub data: Vec<u8>,
+}
+
+impl MimePart {
+    /// The last component of `filename`, so that a name like `../x` cannot
+    /// point outside the directory the part is saved to. `None` when there is
+    /// no such component, as for `..` or an empty name.
+    pub fn file_name(&self) -> Option<&str> {
+        Path::new(self.filename.as_deref()?).file_name()?.to_str()
+    }
+}
+
+#[derive(Debug)]
+pub enum MimeError {
+    MalformedEncodedWord(String),
+    UnsupportedCharset(String),
+    InvalidText(String),
+    InvalidBase64(Base64Error),
+    MissingBoundary,
+}
+
+impl error::Error for MimeError {}
+
+impl std::fmt::Display for MimeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MimeError::MalformedEncodedWord(word) => {
+                write!(f, "Malformed encoded-word \"{}\"", word)
+            }
+            MimeError::UnsupportedCharset(charset) => {
+                write!(f, "Unsupported charset \"{}\"", charset)
+            }
+            MimeError::InvalidText(charset) => {
+                write!(f, "Encoded text is not valid {}", charset)
+            }
+            MimeError::InvalidBase64(e) => write!(f, "Invalid base64: {}", e),
+            MimeError::MissingBoundary => {
+                write!(f, "Multipart message has no boundary parameter")
+            }
+        }
+    }
+}
+
+/// Encodes a body for `Content-Transfer-Encoding: base64`, wrapped at 76 columns.
+pub fn encode_body(data: &[u8]) -> Vec<u8> {
+    let encoded = encode_to_vec(&Base64Config::mime(), data);
+    let mut out = Vec::with_capacity(encoded.len() + encoded.len() / BODY_LINE_WIDTH * 2 + 2);
+    for line in encoded.chunks(BODY_LINE_WIDTH) {
+        out.extend(line);
+        out.extend(b"\r\n");
+    }
+    out
+}
+
+pub fn decode_body(body: &[u8]) -> Result<Vec<u8>, MimeError> {
+    let base64_encoded_bytes: Vec<u8> = body
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace())
+        .collect();
+    decode_to_vec(&Base64Config::mime(), &base64_encoded_bytes).map_err(MimeError::InvalidBase64)
+}
+
+/// Encodes header text as RFC 2047 `B` encoded-words of at most 75 characters,
+/// never splitting a UTF-8 sequence between two words.
+pub fn encode_words(text: &str) -> String {
+    let overhead = "=?".len() + ENCODED_WORD_CHARSET.len() + "?B?".len() + "?=".len();
+    let max_bytes = (MAX_ENCODED_WORD_LENGTH - overhead) / 4 * 3;
+    let mut words = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        let mut end = (start + max_bytes).min(text.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let payload = encode_to_vec(&Base64Config::mime(), &text.as_bytes()[start..end]);
+        words.push(format!(
+            "=?{ENCODED_WORD_CHARSET}?B?{}?=",
+            String::from_utf8_lossy(&payload)
+        ));
+        start = end;
+    }
+    words.join("\r\n ")
+}
+
+fn decode_charset(charset: &str, bytes: Vec<u8>) -> Result<String, MimeError> {
+    // RFC 2231 allows a language suffix, e.g. UTF-8*en
+    let charset = charset.split('*').next().unwrap_or_default();
+    match charset.to_ascii_uppercase().as_str() {
+        "UTF-8" | "US-ASCII" => {
+            String::from_utf8(bytes).map_err(|_| MimeError::InvalidText(charset.to_string()))
+        }
+        "ISO-8859-1" | "LATIN1" => Ok(bytes.into_iter().map(char::from).collect()),
+        _ => Err(MimeError::UnsupportedCharset(charset.to_string())),
+    }
+}
+
+fn decode_q(text: &str) -> Option<Vec<u8>> {
+    let mut bytes = text.bytes();
+    let mut decoded = Vec::with_capacity(text.len());
+    while let Some(b) = bytes.next() {
+        match b {
+            b'_' => decoded.push(b' '),
+            b'=' => {
+                let hex = [bytes.next()?, bytes.next()?];
+                decoded.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+            }
+            b => decoded.push(b),
+        }
+    }
+    Some(decoded)
+}
+
+/// Splits `=?charset?encoding?text?=` into its charset and decoded bytes.
+fn parse_encoded_word(word: &str) -> Result<(&str, Vec<u8>), MimeError> {
+    let malformed = || MimeError::MalformedEncodedWord(word.to_string());
+    let inner = word
+        .strip_prefix("=?")
+        .and_then(|w| w.strip_suffix("?="))
+        .ok_or_else(malformed)?;
+    let mut fields = inner.splitn(3, '?');
+    let (Some(charset), Some(encoding), Some(text)) = (fields.next(), fields.next(), fields.next())
+    else {
+        return Err(malformed());
+    };
+    let bytes = match encoding {
+        "B" | "b" => decode_to_vec(&Base64Config::standard(), text.as_bytes())
+            .map_err(MimeError::InvalidBase64)?,
+        "Q" | "q" => decode_q(text).ok_or_else(malformed)?,
+        _ => return Err(malformed()),
+    };
+    Ok((charset, bytes))
+}
+
+fn encoded_word_end(text: &str) -> Option<usize> {
+    // skip over "=?charset?E?" before looking for the closing "?="
+    let mut question_marks = text.match_indices('?').skip(2);
+    let (text_start, _) = question_marks.next()?;
+    text[text_start + 1..]
+        .find("?=")
+        .map(|end| text_start + 1 + end + 2)
+}
+
+/// Decodes all RFC 2047 encoded-words in a header value. Whitespace between
+/// adjacent encoded-words is dropped, and their bytes are joined before charset
+/// decoding in case an encoder split a multi-byte character.
+pub fn decode_words(header_value: &str) -> Result<String, MimeError> {
+    let mut decoded = String::new();
+    let mut pending: Option<(String, Vec<u8>)> = None;
+    let mut rest = header_value;
+    while !rest.is_empty() {
+        let word_start = rest.find("=?");
+        let word_end = word_start.and_then(|start| encoded_word_end(&rest[start..]));
+        let (Some(start), Some(length)) = (word_start, word_end) else {
+            break;
+        };
+        let (gap, word) = (&rest[..start], &rest[start..start + length]);
+        let (charset, bytes) = parse_encoded_word(word)?;
+        match pending.as_mut() {
+            Some((pending_charset, pending_bytes))
+                if gap.trim().is_empty() && pending_charset.eq_ignore_ascii_case(charset) =>
+            {
+                pending_bytes.extend(bytes);
+            }
+            _ => {
+                if let Some((pending_charset, pending_bytes)) = pending.take() {
+                    decoded.push_str(&decode_charset(&pending_charset, pending_bytes)?);
+                    if !gap.trim().is_empty() {
+                        decoded.push_str(gap);
+                    }
+                } else {
+                    decoded.push_str(gap);
+                }
+                pending = Some((charset.to_string(), bytes));
+            }
+        }
+        rest = &rest[start + length..];
+    }
+    if let Some((charset, bytes)) = pending {
+        decoded.push_str(&decode_charset(&charset, bytes)?);
+    }
+    decoded.push_str(rest);
+    Ok(decoded)
+}
+
+/// Splits a message or part into unfolded `(name, value)` headers and the body.
+fn split_headers(message: &[u8]) -> (Vec<(String, String)>, &[u8]) {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    let mut rest = message;
+    while !rest.is_empty() {
+        let line_end = rest
+            .iter()
+            .position(|b| *b == b'\n')
+            .map_or(rest.len(), |i| i + 1);
+        let (line, remaining) = rest.split_at(line_end);
+        rest = remaining;
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if line.starts_with([' ', '\t']) {
+            if let Some((_, value)) = headers.last_mut() {
+                value.push(' ');
+                value.push_str(line.trim());
+            }
+        } else if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    (headers, rest)
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(n, _)| n == name)
+        .map(|(_, v)| v.as_str())
+}
+
+fn header_parameter(value: &str, name: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|parameter| {
+        let (key, value) = parameter.split_once('=')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn split_multipart<'a>(body: &'a [u8], boundary: &str) -> Vec<&'a [u8]> {
+    let delimiter = format!("--{boundary}");
+    let mut parts = Vec::new();
+    let mut part_start = None;
+    let mut offset = 0;
+    for line in body.split_inclusive(|b| *b == b'\n') {
+        let trimmed = line.trim_ascii_end();
+        if trimmed.starts_with(delimiter.as_bytes()) {
+            if let Some(start) = part_start {
+                parts.push(&body[start..offset]);
+            }
+            if trimmed.ends_with(b"--") && trimmed.len() == delimiter.len() + 2 {
+                break;
+            }
+            part_start = Some(offset + line.len());
+        }
+        offset += line.len();
+    }
+    parts
+}
+
+fn collect_base64_parts(entity: &[u8], parts: &mut Vec<MimePart>) -> Result<(), MimeError> {
+    let (headers, body) = split_headers(entity);
+    let content_type = header(&headers, "content-type").unwrap_or("text/plain");
+    let mime_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if mime_type.starts_with("multipart/") {
+        let boundary =
+            header_parameter(content_type, "boundary").ok_or(MimeError::MissingBoundary)?;
+        for part in split_multipart(body, &boundary) {
+            collect_base64_parts(part, parts)?;
+        }
+        return Ok(());
+    }
+    let is_base64 = header(&headers, "content-transfer-encoding")
+        .is_some_and(|encoding| encoding.trim().eq_ignore_ascii_case("base64"));
+    if !is_base64 {
+        return Ok(());
+    }
+    let filename = header(&headers, "content-disposition")
+        .and_then(|disposition| header_parameter(disposition, "filename"))
+        .or_else(|| header_parameter(content_type, "name"))
+        .map(|name| decode_words(&name).unwrap_or(name));
+    parts.push(MimePart {
+        content_type: mime_type,
+        filename,
+        data: decode_body(body)?,
+    });
+    Ok(())
+}
+
+/// Decodes every base64 transfer-encoded part of a (possibly nested multipart) message.
+pub fn base64_parts(message: &[u8]) -> Result<Vec<MimePart>, MimeError> {
+    let mut parts = Vec::new();
+    collect_base64_parts(message, &mut parts)?;
+    Ok(parts)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,163 @@
+use super::*;
+
+const MULTIPART_EML: &str = "From: sender@example.com\r
+To: receiver@example.com\r
+Subject: =?UTF-8?B?UmVwb3J0?=\r
+MIME-Version: 1.0\r
+Content-Type: multipart/mixed;\r
+ boundary=\"outer\"\r
+\r
+This is a multi-part message in MIME format.\r
+--outer\r
+Content-Type: multipart/alternative; boundary=inner\r
+\r
+--inner\r
+Content-Type: text/plain; charset=UTF-8\r
+Content-Transfer-Encoding: 7bit\r
+\r
+Plain text body\r
+--inner\r
+Content-Type: text/html; charset=UTF-8\r
+Content-Transfer-Encoding: base64\r
+\r
+PHA+SGVsbG88L3A+\r
+--inner--\r
+--outer\r
+Content-Type: application/octet-stream; name=\"data.bin\"\r
+Content-Transfer-Encoding: BASE64\r
+Content-Disposition: attachment;\r
+ filename=\"=?UTF-8?B?w6R0dGFjaG1lbnQuYmlu?=\"\r
+\r
+AAECAwQF\r
+BgcICQ==\r
+--outer--\r
+";
+
+#[test]
+fn test_encode_body_wraps_at_76_columns() {
+    let encoded = encode_body(&[0u8; 100]);
+    let lines: Vec<&[u8]> = encoded.split(|b| *b == b'\n').collect();
+    assert_eq!(lines[0].len(), 77);
+    assert!(lines[0].ends_with(b"\r"));
+    assert_eq!(lines[1], format!("{}==\r", "A".repeat(58)).as_bytes());
+    let decoded = decode_body(&encoded).expect("decode should succeed");
+    assert_eq!(decoded, [0u8; 100]);
+}
+
+#[test]
+fn test_decode_body_requires_padding() {
+    match decode_body(b"AAE\r\n") {
+        Err(MimeError::InvalidBase64(Base64Error::InvalidLength(3, b'='))) => {}
+        other => panic!("expected InvalidBase64, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_encode_words_short() {
+    assert_eq!(encode_words("Hello"), "=?UTF-8?B?SGVsbG8=?=");
+    assert_eq!(encode_words(""), "");
+}
+
+#[test]
+fn test_encode_words_respects_length_limit_and_utf8_boundaries() {
+    let text = format!("a{}", "€".repeat(40));
+    let encoded = encode_words(&text);
+    let words: Vec<&str> = encoded.split("\r\n ").collect();
+    assert!(words.len() > 1);
+    for word in &words {
+        assert!(word.len() <= 75, "{word} is {} characters", word.len());
+        // every word decodes to complete characters on its own
+        let (_, bytes) = parse_encoded_word(word).expect("word is well formed");
+        assert!(String::from_utf8(bytes).is_ok());
+    }
+    assert_eq!(decode_words(&encoded).expect("decode should succeed"), text);
+}
+
+#[test]
+fn test_decode_words() {
+    let decoded = decode_words("=?UTF-8?B?SGVsbG8=?= =?utf-8?q?_W=C3=B6rld?=").unwrap();
+    assert_eq!(decoded, "Hello Wörld");
+    let decoded = decode_words("Re: =?ISO-8859-1?Q?Caf=E9?= is open").unwrap();
+    assert_eq!(decoded, "Re: Café is open");
+    let decoded = decode_words("no encoded words here").unwrap();
+    assert_eq!(decoded, "no encoded words here");
+}
+
+#[test]
+fn test_decode_words_joins_split_characters() {
+    let decoded = decode_words("=?UTF-8?Q?=C3?=\r\n =?UTF-8?Q?=A9?=").unwrap();
+    assert_eq!(decoded, "é");
+}
+
+#[test]
+fn test_decode_words_errors() {
+    match decode_words("=?KOI8-R?B?SGVsbG8=?=") {
+        Err(MimeError::UnsupportedCharset(charset)) if charset == "KOI8-R" => {}
+        other => panic!("expected UnsupportedCharset, got {:?}", other),
+    }
+    match decode_words("=?UTF-8?X?abc?=") {
+        Err(MimeError::MalformedEncodedWord(_)) => {}
+        other => panic!("expected MalformedEncodedWord, got {:?}", other),
+    }
+    match decode_words("=?UTF-8?B?/w==?=") {
+        Err(MimeError::InvalidText(charset)) if charset == "UTF-8" => {}
+        other => panic!("expected InvalidText, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_base64_parts_of_nested_multipart() {
+    let parts = base64_parts(MULTIPART_EML.as_bytes()).expect("message should parse");
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].content_type, "text/html");
+    assert_eq!(parts[0].filename, None);
+    assert_eq!(parts[0].data, b"<p>Hello</p>");
+    assert_eq!(parts[1].content_type, "application/octet-stream");
+    assert_eq!(parts[1].filename.as_deref(), Some("\u{e4}ttachment.bin"));
+    assert_eq!(parts[1].data, (0..10).collect::<Vec<u8>>());
+}
+
+#[test]
+fn test_file_name_stays_in_the_output_directory() {
+    let part = |filename: Option<&str>| MimePart {
+        content_type: "application/octet-stream".to_string(),
+        filename: filename.map(str::to_string),
+        data: Vec::new(),
+    };
+    assert_eq!(part(Some("report.pdf")).file_name(), Some("report.pdf"));
+    assert_eq!(
+        part(Some("../../.ssh/authorized_keys")).file_name(),
+        Some("authorized_keys")
+    );
+    assert_eq!(part(Some("/etc/passwd")).file_name(), Some("passwd"));
+    assert_eq!(part(Some("..")).file_name(), None);
+    assert_eq!(part(Some("")).file_name(), None);
+    assert_eq!(part(None).file_name(), None);
+}
+
+#[test]
+fn test_base64_single_part_message() {
+    let message = b"Content-Type: text/plain\nContent-Transfer-Encoding: base64\n\nSGk=\n";
+    let parts = base64_parts(message).expect("message should parse");
+    assert_eq!(parts[0].data, b"Hi");
+    let parts = base64_parts(b"Subject: plain\n\nHi\n").expect("message should parse");
+    assert!(parts.is_empty());
+}
+
+#[test]
+fn test_multipart_without_boundary() {
+    match base64_parts(b"Content-Type: multipart/mixed\n\nbody\n") {
+        Err(MimeError::MissingBoundary) => {}
+        other => panic!("expected MissingBoundary, got {:?}", other.map(|p| p.len())),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", MimeError::MalformedEncodedWord("=?x".into())).contains("=?x"));
+    assert!(format!("{}", MimeError::UnsupportedCharset("KOI8-R".into())).contains("KOI8-R"));
+    assert!(format!("{}", MimeError::InvalidText("UTF-8".into())).contains("UTF-8"));
+    let e = MimeError::InvalidBase64(Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("Invalid character"));
+    assert!(format!("{}", MimeError::MissingBoundary).contains("boundary"));
+}
@@ -0,0 +1,152 @@
+use crate::base45::{self, Base45Error};
+use crate::base64::{self, Base64Config, Base64Error, Padding};
+use crate::radix::{self, RadixConfig, RadixError, RadixMode};
+use std::error;
+
+/// The multibase encodings this crate can produce, identified by the first
+/// character of a multibase string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base {
+    /// `k`: lowercase base36, as used for IPNS keys in subdomains
+    Base36,
+    /// `K`
+    Base36Upper,
+    /// `R`
+    Base45,
+    /// `z`: Bitcoin base58, the default for CIDv0 style identifiers
+    Base58Btc,
+    /// `m`: RFC 4648 base64 without padding
+    Base64,
+    /// `M`
+    Base64Pad,
+    /// `u`: RFC 4648 base64url without padding
+    Base64Url,
+    /// `U`
+    Base64UrlPad,
+}
+
+const BASES: [Base; 8] = [
+    Base::Base36,
+    Base::Base36Upper,
+    Base::Base45,
+    Base::Base58Btc,
+    Base::Base64,
+    Base::Base64Pad,
+    Base::Base64Url,
+    Base::Base64UrlPad,
+];
+
+#[derive(Debug)]
+pub enum MultibaseError {
+    EmptyInput,
+    UnknownPrefix(char),
+    InvalidBase64(Base, Base64Error),
+    InvalidRadix(Base, RadixError),
+    InvalidBase45(Base45Error),
+}
+
+impl error::Error for MultibaseError {}
+
+impl std::fmt::Display for MultibaseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultibaseError::EmptyInput => write!(f, "Empty input has no multibase prefix"),
+            MultibaseError::UnknownPrefix(c) => {
+                write!(f, "Unknown or unsupported multibase prefix \'{}\'", *c)
+            }
+            MultibaseError::InvalidBase64(base, e) => write!(f, "Invalid {}: {}", base.name(), e),
+            MultibaseError::InvalidRadix(base, e) => write!(f, "Invalid {}: {}", base.name(), e),
+            MultibaseError::InvalidBase45(e) => write!(f, "Invalid base45: {}", e),
+        }
+    }
+}
+
+impl Base {
+    pub fn prefix(self) -> char {
+        match self {
+            Base::Base36 => 'k',
+            Base::Base36Upper => 'K',
+            Base::Base45 => 'R',
+            Base::Base58Btc => 'z',
+            Base::Base64 => 'm',
+            Base::Base64Pad => 'M',
+            Base::Base64Url => 'u',
+            Base::Base64UrlPad => 'U',
+        }
+    }
+
+    pub fn from_prefix(prefix: char) -> Option<Base> {
+        BASES.into_iter().find(|base| base.prefix() == prefix)
+    }
+
+    /// The name from the multibase table, e.g. `base64urlpad`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Base::Base36 => "base36",
+            Base::Base36Upper => "base36upper",
+            Base::Base45 => "base45",
+            Base::Base58Btc => "base58btc",
+            Base::Base64 => "base64",
+            Base::Base64Pad => "base64pad",
+            Base::Base64Url => "base64url",
+            Base::Base64UrlPad => "base64urlpad",
+        }
+    }
+
+    fn base64_config(self) -> Option<Base64Config> {
+        let (config, padding) = match self {
+            Base::Base64 => (Base64Config::standard(), Padding::NoPadding),
+            Base::Base64Pad => (Base64Config::standard(), Padding::Required(b'=')),
+            Base::Base64Url => (Base64Config::url(), Padding::NoPadding),
+            Base::Base64UrlPad => (Base64Config::url(), Padding::Required(b'=')),
+            _ => return None,
+        };
+        Some(config.with_padding(padding).unwrap())
+    }
+
+    fn radix_config(self) -> Option<RadixConfig> {
+        // multibase keeps leading zero bytes for every radix encoding
+        let mode = RadixMode::PreserveLeadingZeros;
+        match self {
+            Base::Base36 => Some(RadixConfig::base36(mode)),
+            Base::Base36Upper => {
+                Some(RadixConfig::new(vec![b'0'..=b'9', b'A'..=b'Z'], mode).unwrap())
+            }
+            Base::Base58Btc => Some(RadixConfig::base58(mode)),
+            _ => None,
+        }
+    }
+}
+
+pub fn encode(base: Base, bytes: &[u8]) -> String {
+    let encoded = if let Some(config) = base.base64_config() {
+        base64::encode_to_vec(&config, bytes)
+    } else if let Some(config) = base.radix_config() {
+        radix::encode_to_vec(&config, bytes)
+    } else {
+        base45::encode_to_vec(bytes)
+    };
+    let mut multibase = String::from(base.prefix());
+    multibase.push_str(&String::from_utf8_lossy(&encoded));
+    multibase
+}
+
+/// Decodes a multibase string, returning the base its prefix selected
+/// along with the decoded bytes.
+pub fn decode(multibase: &str) -> Result<(Base, Vec<u8>), MultibaseError> {
+    let mut chars = multibase.chars();
+    let prefix = chars.next().ok_or(MultibaseError::EmptyInput)?;
+    let base = Base::from_prefix(prefix).ok_or(MultibaseError::UnknownPrefix(prefix))?;
+    let body = chars.as_str().as_bytes();
+    let decoded = if let Some(config) = base.base64_config() {
+        base64::decode_to_vec(&config, body).map_err(|e| MultibaseError::InvalidBase64(base, e))?
+    } else if let Some(config) = base.radix_config() {
+        radix::decode_to_vec(&config, body).map_err(|e| MultibaseError::InvalidRadix(base, e))?
+    } else {
+        base45::decode_to_vec(body).map_err(MultibaseError::InvalidBase45)?
+    };
+    Ok((base, decoded))
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,96 @@
+use super::*;
+
+// "yes mani !" vectors from the multibase specification
+const VECTORS: [(Base, &str); 7] = [
+    (Base::Base36, "k2lcpzo5yikidynfl"),
+    (Base::Base36Upper, "K2LCPZO5YIKIDYNFL"),
+    (Base::Base58Btc, "z7paNL19xttacUY"),
+    (Base::Base64, "meWVzIG1hbmkgIQ"),
+    (Base::Base64Pad, "MeWVzIG1hbmkgIQ=="),
+    (Base::Base64Url, "ueWVzIG1hbmkgIQ"),
+    (Base::Base64UrlPad, "UeWVzIG1hbmkgIQ=="),
+];
+
+#[test]
+fn test_spec_vectors() {
+    for (base, expected) in VECTORS {
+        assert_eq!(encode(base, b"yes mani !"), expected, "{}", base.name());
+        let (detected, decoded) = decode(expected).expect("decode should succeed");
+        assert_eq!(detected, base);
+        assert_eq!(decoded, b"yes mani !");
+    }
+}
+
+#[test]
+fn test_leading_zeros_are_kept() {
+    assert_eq!(encode(Base::Base58Btc, b"\0yes mani !"), "z17paNL19xttacUY");
+    assert_eq!(encode(Base::Base36, b"\0yes mani !"), "k02lcpzo5yikidynfl");
+    let (_, decoded) = decode("z17paNL19xttacUY").unwrap();
+    assert_eq!(decoded, b"\0yes mani !");
+}
+
+#[test]
+fn test_base45_roundtrip() {
+    let encoded = encode(Base::Base45, b"yes mani !");
+    assert!(encoded.starts_with('R'));
+    let (base, decoded) = decode(&encoded).expect("decode should succeed");
+    assert_eq!(base, Base::Base45);
+    assert_eq!(decoded, b"yes mani !");
+}
+
+#[test]
+fn test_empty_payload() {
+    for base in BASES {
+        let encoded = encode(base, b"");
+        assert_eq!(encoded, base.prefix().to_string());
+        assert_eq!(decode(&encoded).unwrap(), (base, vec![]));
+    }
+}
+
+#[test]
+fn test_padding_follows_the_prefix() {
+    match decode("meWVzIG1hbmkgIQ==") {
+        Err(MultibaseError::InvalidBase64(Base::Base64, _)) => {}
+        other => panic!("expected InvalidBase64 for padded 'm', got {:?}", other),
+    }
+    match decode("MeWVzIG1hbmkgIQ") {
+        Err(MultibaseError::InvalidBase64(
+            Base::Base64Pad,
+            Base64Error::InvalidLength(14, b'='),
+        )) => {}
+        other => panic!("expected InvalidLength for unpadded 'M', got {:?}", other),
+    }
+}
+
+#[test]
+fn test_decode_errors() {
+    assert!(matches!(decode(""), Err(MultibaseError::EmptyInput)));
+    assert!(matches!(
+        decode("bnbswy3dp"),
+        Err(MultibaseError::UnknownPrefix('b'))
+    ));
+    assert!(matches!(
+        decode("\u{1F600}abc"),
+        Err(MultibaseError::UnknownPrefix('\u{1F600}'))
+    ));
+    match decode("z0OIl") {
+        Err(MultibaseError::InvalidRadix(Base::Base58Btc, RadixError::InvalidCharacter(b'0'))) => {}
+        other => panic!("expected InvalidRadix, got {:?}", other),
+    }
+    assert!(matches!(
+        decode("Rab"),
+        Err(MultibaseError::InvalidBase45(_))
+    ));
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", MultibaseError::EmptyInput).contains("Empty input"));
+    assert!(format!("{}", MultibaseError::UnknownPrefix('b')).contains("\'b\'"));
+    let e = MultibaseError::InvalidBase64(Base::Base64UrlPad, Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("base64urlpad"));
+    let e = MultibaseError::InvalidRadix(Base::Base58Btc, RadixError::InvalidCharacter(b'0'));
+    assert!(format!("{e}").contains("base58btc"));
+    let e = MultibaseError::InvalidBase45(Base45Error::InvalidLength(1));
+    assert!(format!("{e}").contains("base45"));
+}
@@ -0,0 +1,154 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use std::error;
+
+const LINE_WIDTH: usize = 64;
+const BEGIN_PREFIX: &str = "-----BEGIN ";
+const END_PREFIX: &str = "-----END ";
+const BOUNDARY_SUFFIX: &str = "-----";
+
+pub struct PemBlock {
+    pub label: String,
+    /// RFC 1421 encapsulated headers, e.g. `Proc-Type: 4,ENCRYPTED`, in order of appearance
+    pub headers: Vec<(String, String)>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum PemError {
+    InvalidUtf8,
+    MissingEndLine(String),
+    MismatchedLabels(String, String),
+    InvalidHeader(String),
+    InvalidBase64(String, Base64Error),
+}
+
+impl error::Error for PemError {}
+
+impl std::fmt::Display for PemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PemError::InvalidUtf8 => write!(f, "PEM input is not valid UTF-8"),
+            PemError::MissingEndLine(label) => {
+                write!(f, "Missing \"-----END {}-----\" line", label)
+            }
+            PemError::MismatchedLabels(begin, end) => {
+                write!(
+                    f,
+                    "BEGIN label \"{}\" does not match END label \"{}\"",
+                    begin, end
+                )
+            }
+            PemError::InvalidHeader(line) => write!(f, "Invalid header line \"{}\"", line),
+            PemError::InvalidBase64(label, e) => {
+                write!(f, "Invalid base64 in \"{}\" block: {}", label, e)
+            }
+        }
+    }
+}
+
+impl PemBlock {
+    pub fn new(label: &str, data: Vec<u8>) -> Self {
+        PemBlock {
+            label: label.to_string(),
+            headers: Vec::new(),
+            data,
+        }
+    }
+
+    pub fn header(&self, key: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn boundary_label<'a>(line: &'a str, prefix: &str) -> Option<&'a str> {
+    line.strip_prefix(prefix)?.strip_suffix(BOUNDARY_SUFFIX)
+}
+
+fn parse_headers<'a>(
+    lines: &mut std::iter::Peekable<impl Iterator<Item = &'a str>>,
+) -> Result<Vec<(String, String)>, PemError> {
+    let mut headers: Vec<(String, String)> = Vec::new();
+    match lines.peek() {
+        Some(line) if line.contains(':') => {}
+        _ => return Ok(headers),
+    }
+    for line in lines.by_ref() {
+        if line.trim().is_empty() {
+            break;
+        }
+        // continuation lines start with whitespace and extend the previous value
+        if line.starts_with([' ', '\t']) {
+            let (_, value) = headers
+                .last_mut()
+                .ok_or_else(|| PemError::InvalidHeader(line.to_string()))?;
+            value.push(' ');
+            value.push_str(line.trim());
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| PemError::InvalidHeader(line.to_string()))?;
+        headers.push((key.trim().to_string(), value.trim().to_string()));
+    }
+    Ok(headers)
+}
+
+/// Parses every PEM block in `bytes`, ignoring any explanatory text around them.
+pub fn parse(bytes: &[u8]) -> Result<Vec<PemBlock>, PemError> {
+    let text = std::str::from_utf8(bytes).map_err(|_| PemError::InvalidUtf8)?;
+    let mut lines = text.lines().map(|line| line.trim_end()).peekable();
+    let mut blocks = Vec::new();
+    while let Some(line) = lines.next() {
+        let Some(label) = boundary_label(line.trim_start(), BEGIN_PREFIX) else {
+            continue;
+        };
+        let headers = parse_headers(&mut lines)?;
+        let mut base64_encoded_bytes = Vec::new();
+        let end_label = loop {
+            let line = lines
+                .next()
+                .ok_or_else(|| PemError::MissingEndLine(label.to_string()))?
+                .trim_start();
+            if let Some(end_label) = boundary_label(line, END_PREFIX) {
+                break end_label;
+            }
+            base64_encoded_bytes.extend(line.bytes().filter(|b| !b.is_ascii_whitespace()));
+        };
+        if end_label != label {
+            return Err(PemError::MismatchedLabels(
+                label.to_string(),
+                end_label.to_string(),
+            ));
+        }
+        let data = decode_to_vec(&Base64Config::mime(), &base64_encoded_bytes)
+            .map_err(|e| PemError::InvalidBase64(label.to_string(), e))?;
+        blocks.push(PemBlock {
+            label: label.to_string(),
+            headers,
+            data,
+        });
+    }
+    Ok(blocks)
+}
+
+pub fn encode(block: &PemBlock) -> Vec<u8> {
+    let mut out = format!("{BEGIN_PREFIX}{}{BOUNDARY_SUFFIX}\n", block.label).into_bytes();
+    for (key, value) in &block.headers {
+        out.extend(format!("{key}: {value}\n").into_bytes());
+    }
+    if !block.headers.is_empty() {
+        out.push(b'\n');
+    }
+    for line in encode_to_vec(&Base64Config::mime(), &block.data).chunks(LINE_WIDTH) {
+        out.extend(line);
+        out.push(b'\n');
+    }
+    out.extend(format!("{END_PREFIX}{}{BOUNDARY_SUFFIX}\n", block.label).into_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests;
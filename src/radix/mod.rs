@@ -0,0 +1,207 @@
+use std::error;
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Copy)]
+pub enum RadixMode {
+    /// Treat the input as one big-endian unsigned integer; leading zero bytes are dropped
+    Integer,
+    /// Encode each leading zero byte as one zero symbol, like Base58 does
+    PreserveLeadingZeros,
+}
+
+pub struct RadixConfig {
+    ranges: Vec<RangeInclusive<u8>>,
+    mode: RadixMode,
+}
+
+#[derive(Debug)]
+pub enum RadixConfigError {
+    OverlappingRanges(RangeInclusive<u8>, RangeInclusive<u8>),
+    TooFewSymbols(usize),
+}
+
+#[derive(Debug)]
+pub enum RadixError {
+    InvalidCharacter(u8),
+    EmptyInput,
+}
+
+impl error::Error for RadixConfigError {}
+impl error::Error for RadixError {}
+
+impl std::fmt::Display for RadixConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RadixConfigError::OverlappingRanges(range1, range2) => {
+                write!(
+                    f,
+                    "Overlapping ranges {}..={} and {}..={}",
+                    *range1.start() as char,
+                    *range1.end() as char,
+                    *range2.start() as char,
+                    *range2.end() as char
+                )
+            }
+            RadixConfigError::TooFewSymbols(count) => {
+                write!(f, "Alphabet has {} symbols, at least 2 required", *count)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for RadixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RadixError::InvalidCharacter(c) => {
+                write!(f, "Invalid character \'{}\'", *c as char)
+            }
+            RadixError::EmptyInput => {
+                write!(f, "Empty input does not represent an integer")
+            }
+        }
+    }
+}
+
+impl RadixConfig {
+    pub fn new(ranges: Vec<RangeInclusive<u8>>, mode: RadixMode) -> Result<Self, RadixConfigError> {
+        let config = Self { ranges, mode };
+        validate_config(&config)?;
+        Ok(config)
+    }
+    pub fn base62(mode: RadixMode) -> Self {
+        RadixConfig::new(vec![b'0'..=b'9', b'A'..=b'Z', b'a'..=b'z'], mode).unwrap()
+    }
+    pub fn base36(mode: RadixMode) -> Self {
+        RadixConfig::new(vec![b'0'..=b'9', b'a'..=b'z'], mode).unwrap()
+    }
+    /// The Bitcoin alphabet, which leaves out `0`, `O`, `I` and `l`
+    pub fn base58(mode: RadixMode) -> Self {
+        RadixConfig::new(
+            vec![
+                b'1'..=b'9',
+                b'A'..=b'H',
+                b'J'..=b'N',
+                b'P'..=b'Z',
+                b'a'..=b'k',
+                b'm'..=b'z',
+            ],
+            mode,
+        )
+        .unwrap()
+    }
+
+    fn radix(&self) -> u32 {
+        self.ranges.iter().map(|r| r.len() as u32).sum()
+    }
+}
+
+fn validate_config(config: &RadixConfig) -> Result<(), RadixConfigError> {
+    let ranges = &config.ranges;
+    for i in 0..ranges.len() {
+        for j in i + 1..ranges.len() {
+            let (r1, r2) = (&ranges[i], &ranges[j]);
+            if r1.start() <= r2.end() && r2.start() <= r1.end() {
+                return Err(RadixConfigError::OverlappingRanges(r1.clone(), r2.clone()));
+            }
+        }
+    }
+    let len_sum = config.ranges.iter().map(|r| r.len()).sum::<usize>();
+    if len_sum < 2 {
+        return Err(RadixConfigError::TooFewSymbols(len_sum));
+    }
+    Ok(())
+}
+
+fn decode_symbol(config: &RadixConfig, b: u8) -> Result<u8, RadixError> {
+    let mut offset = 0usize;
+    for r in &config.ranges {
+        if r.contains(&b) {
+            return Ok(((b - r.start()) as usize + offset) as u8);
+        }
+        offset += r.len();
+    }
+    Err(RadixError::InvalidCharacter(b))
+}
+
+fn encode_symbol(config: &RadixConfig, digit: u8) -> u8 {
+    let mut digit_minus_offset = digit as usize;
+    for r in &config.ranges {
+        if digit_minus_offset < r.len() {
+            return r.start() + digit_minus_offset as u8;
+        }
+        digit_minus_offset -= r.len();
+    }
+    0u8
+}
+
+/// Converts big-endian digits from one radix to another, dropping leading zeroes.
+fn convert_radix(digits: impl Iterator<Item = u8>, from: u32, to: u32) -> Vec<u8> {
+    let mut converted_le: Vec<u8> = Vec::new();
+    for digit in digits {
+        let mut carry = digit as u32;
+        for converted_digit in converted_le.iter_mut() {
+            let value = *converted_digit as u32 * from + carry;
+            *converted_digit = (value % to) as u8;
+            carry = value / to;
+        }
+        while carry != 0 {
+            converted_le.push((carry % to) as u8);
+            carry /= to;
+        }
+    }
+    converted_le.reverse();
+    converted_le
+}
+
+pub fn decode(
+    config: &RadixConfig,
+    radix_encoded_bytes: &[u8],
+) -> Result<impl Iterator<Item = u8> + use<>, RadixError> {
+    let digits = radix_encoded_bytes
+        .iter()
+        .map(|b| decode_symbol(config, *b))
+        .collect::<Result<Vec<u8>, RadixError>>()?;
+    let (leading_zeroes, bytes) = match config.mode {
+        RadixMode::Integer if digits.is_empty() => return Err(RadixError::EmptyInput),
+        RadixMode::Integer => match convert_radix(digits.into_iter(), config.radix(), 256) {
+            bytes if bytes.is_empty() => (1, bytes),
+            bytes => (0, bytes),
+        },
+        RadixMode::PreserveLeadingZeros => {
+            let leading_zeroes = digits.iter().take_while(|d| **d == 0).count();
+            let significant_digits = digits.into_iter().skip(leading_zeroes);
+            let bytes = convert_radix(significant_digits, config.radix(), 256);
+            (leading_zeroes, bytes)
+        }
+    };
+    Ok(std::iter::repeat_n(0u8, leading_zeroes).chain(bytes))
+}
+
+pub fn decode_to_vec(
+    config: &RadixConfig,
+    radix_encoded_bytes: &[u8],
+) -> Result<Vec<u8>, RadixError> {
+    let decoded_iter = decode(config, radix_encoded_bytes)?;
+    Ok(Vec::from_iter(decoded_iter))
+}
+
+pub fn encode<'a>(config: &'a RadixConfig, bytes: &[u8]) -> impl Iterator<Item = u8> + use<'a> {
+    let leading_zeroes = bytes.iter().take_while(|b| **b == 0).count();
+    let digits = convert_radix(bytes.iter().copied(), 256, config.radix());
+    let zero_digits = match config.mode {
+        RadixMode::Integer if digits.is_empty() => 1,
+        RadixMode::Integer => 0,
+        RadixMode::PreserveLeadingZeros => leading_zeroes,
+    };
+    std::iter::repeat_n(0u8, zero_digits)
+        .chain(digits)
+        .map(|digit| encode_symbol(config, digit))
+}
+
+pub fn encode_to_vec(config: &RadixConfig, bytes: &[u8]) -> Vec<u8> {
+    let encoded_iter = encode(config, bytes);
+    Vec::from_iter(encoded_iter)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,119 @@
+use super::*;
+
+#[test]
+fn test_base62_known_vectors() {
+    let cfg = RadixConfig::base62(RadixMode::Integer);
+    assert_eq!(encode_to_vec(&cfg, b"hello world"), b"AAwf93rvy4aWQVw");
+    assert_eq!(encode_to_vec(&cfg, &u64::MAX.to_be_bytes()), b"LygHa16AHYF");
+    let decoded = decode_to_vec(&cfg, b"AAwf93rvy4aWQVw").expect("decode should succeed");
+    assert_eq!(decoded, b"hello world");
+}
+
+#[test]
+fn test_base36_known_vectors() {
+    let cfg = RadixConfig::base36(RadixMode::Integer);
+    assert_eq!(
+        encode_to_vec(&cfg, &u64::MAX.to_be_bytes()),
+        b"3w5e11264sgsf"
+    );
+    let decoded = decode_to_vec(&cfg, b"3w5e11264sgsf").expect("decode should succeed");
+    assert_eq!(decoded, u64::MAX.to_be_bytes());
+}
+
+#[test]
+fn test_integer_mode_drops_leading_zeroes() {
+    let cfg = RadixConfig::base36(RadixMode::Integer);
+    assert_eq!(encode_to_vec(&cfg, &[0, 0, 1, 0]), b"74");
+    let decoded = decode_to_vec(&cfg, b"0074").expect("decode should succeed");
+    assert_eq!(decoded, [1, 0]);
+}
+
+#[test]
+fn test_integer_mode_zero() {
+    let cfg = RadixConfig::base62(RadixMode::Integer);
+    assert_eq!(encode_to_vec(&cfg, &[]), b"0");
+    assert_eq!(encode_to_vec(&cfg, &[0, 0]), b"0");
+    let decoded = decode_to_vec(&cfg, b"000").expect("decode should succeed");
+    assert_eq!(decoded, [0]);
+    match decode_to_vec(&cfg, b"") {
+        Err(RadixError::EmptyInput) => {}
+        other => panic!("expected EmptyInput, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_preserve_leading_zeroes_roundtrip() {
+    let cfg = RadixConfig::base62(RadixMode::PreserveLeadingZeros);
+    assert_eq!(encode_to_vec(&cfg, &[0, 0, 1, 0]), b"0048");
+    assert_eq!(encode_to_vec(&cfg, &[]), b"");
+    for input in [&[][..], &[0], &[0, 0], &[0, 255, 0], &[1, 2, 3, 4, 5]] {
+        let encoded = encode_to_vec(&cfg, input);
+        let decoded = decode_to_vec(&cfg, &encoded).expect("decode should succeed");
+        assert_eq!(decoded, input);
+    }
+}
+
+#[test]
+fn test_custom_alphabet() {
+    // binary with '.' and '#' as digits
+    let cfg =
+        RadixConfig::new(vec![b'.'..=b'.', b'#'..=b'#'], RadixMode::Integer).expect("valid config");
+    assert_eq!(encode_to_vec(&cfg, &[5]), b"#.#");
+    assert_eq!(
+        decode_to_vec(&cfg, b"#.#").expect("decode should succeed"),
+        [5]
+    );
+
+    // a full 256 symbol alphabet is the identity on integers
+    let cfg =
+        RadixConfig::new(vec![0..=255], RadixMode::PreserveLeadingZeros).expect("valid config");
+    assert_eq!(encode_to_vec(&cfg, &[0, 7, 255]), [0, 7, 255]);
+}
+
+#[test]
+fn test_config_validation() {
+    match RadixConfig::new(vec![b'0'..=b'9', b'9'..=b'z'], RadixMode::Integer) {
+        Err(RadixConfigError::OverlappingRanges(_, _)) => {}
+        Err(e) => panic!("expected OverlappingRanges, got {:?}", e),
+        Ok(_) => panic!("expected Err, got Ok"),
+    }
+    match RadixConfig::new(vec![b'0'..=b'0'], RadixMode::Integer) {
+        Err(RadixConfigError::TooFewSymbols(1)) => {}
+        Err(e) => panic!("expected TooFewSymbols(1), got {:?}", e),
+        Ok(_) => panic!("expected Err, got Ok"),
+    }
+}
+
+#[test]
+fn test_base58_bitcoin_vectors() {
+    let cfg = RadixConfig::base58(RadixMode::PreserveLeadingZeros);
+    assert_eq!(encode_to_vec(&cfg, b"Hello World!"), b"2NEpo7TZRRrLZSi2U");
+    assert_eq!(
+        encode_to_vec(&cfg, &[0, 0, 0x28, 0x7f, 0xb4, 0xcd]),
+        b"11233QC4"
+    );
+    let decoded = decode_to_vec(&cfg, b"11233QC4").expect("decode should succeed");
+    assert_eq!(decoded, [0, 0, 0x28, 0x7f, 0xb4, 0xcd]);
+    match decode_to_vec(&cfg, b"0OIl") {
+        Err(RadixError::InvalidCharacter(b'0')) => {}
+        other => panic!("expected InvalidCharacter('0'), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_character() {
+    let cfg = RadixConfig::base36(RadixMode::Integer);
+    match decode_to_vec(&cfg, b"3W5E") {
+        Err(RadixError::InvalidCharacter(b'W')) => {}
+        other => panic!("expected InvalidCharacter('W'), got {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    let ovr = RadixConfigError::OverlappingRanges(b'0'..=b'9', b'9'..=b'z');
+    assert!(format!("{ovr}").contains("Overlapping ranges"));
+    assert!(format!("{}", RadixConfigError::TooFewSymbols(1)).contains("at least 2"));
+    assert!(format!("{}", RadixError::InvalidCharacter(b'W')).contains("Invalid character"));
+    assert!(format!("{}", RadixError::EmptyInput).contains("Empty input"));
+}
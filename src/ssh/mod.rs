@@ -0,0 +1,237 @@
+use crate::base64::{Base64Config, Base64Error, decode_to_vec, encode_to_vec};
+use sha2::{Digest, Sha256};
+use std::error;
+
+/// Key type name, names of the wire fields after it, and the label `ssh-keygen -l` prints
+const KEY_TYPES: &[(&str, &[&str], &str)] = &[
+    ("ssh-ed25519", &["pk"], "ED25519"),
+    ("ssh-rsa", &["e", "n"], "RSA"),
+    ("ssh-dss", &["p", "q", "g", "y"], "DSA"),
+    ("ecdsa-sha2-nistp256", &["curve", "Q"], "ECDSA"),
+    ("ecdsa-sha2-nistp384", &["curve", "Q"], "ECDSA"),
+    ("ecdsa-sha2-nistp521", &["curve", "Q"], "ECDSA"),
+    (
+        "sk-ssh-ed25519@openssh.com",
+        &["pk", "application"],
+        "ED25519-SK",
+    ),
+    (
+        "sk-ecdsa-sha2-nistp256@openssh.com",
+        &["curve", "Q", "application"],
+        "ECDSA-SK",
+    ),
+];
+
+const ED25519_KEY_LENGTH: usize = 32;
+
+pub struct PublicKey {
+    /// Leading authorized_keys options such as `no-pty,from="10.0.0.0/8"`
+    pub options: Option<String>,
+    pub key_type: String,
+    /// The wire fields after the key type, in the order of [`PublicKey::field_names`]
+    pub fields: Vec<Vec<u8>>,
+    /// The decoded base64 blob, which is what the fingerprint is taken over
+    pub blob: Vec<u8>,
+    pub comment: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum SshKeyError {
+    MissingKeyData,
+    UnsupportedKeyType(String),
+    InvalidBase64(Base64Error),
+    Truncated(usize),
+    TrailingBytes(usize),
+    KeyTypeMismatch(String, String),
+    InvalidField(&'static str),
+}
+
+impl error::Error for SshKeyError {}
+
+impl std::fmt::Display for SshKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SshKeyError::MissingKeyData => write!(f, "No base64 key data after the key type"),
+            SshKeyError::UnsupportedKeyType(key_type) => {
+                write!(f, "Unsupported key type \"{}\"", key_type)
+            }
+            SshKeyError::InvalidBase64(e) => write!(f, "Invalid base64 key data: {}", e),
+            SshKeyError::Truncated(position) => {
+                write!(f, "Key data ends inside a field at offset {}", *position)
+            }
+            SshKeyError::TrailingBytes(count) => {
+                write!(f, "{} unexpected bytes after the last field", *count)
+            }
+            SshKeyError::KeyTypeMismatch(outer, inner) => write!(
+                f,
+                "Key type \"{}\" does not match \"{}\" inside the key data",
+                outer, inner
+            ),
+            SshKeyError::InvalidField(name) => write!(f, "Invalid \"{}\" field", name),
+        }
+    }
+}
+
+fn key_type_entry(
+    key_type: &str,
+) -> Option<&'static (&'static str, &'static [&'static str], &'static str)> {
+    KEY_TYPES.iter().find(|(name, _, _)| *name == key_type)
+}
+
+/// Reads one SSH wire `string`: a big-endian u32 length followed by that many bytes.
+fn read_string<'a>(blob: &'a [u8], position: &mut usize) -> Result<&'a [u8], SshKeyError> {
+    let truncated = SshKeyError::Truncated(*position);
+    let length_bytes = blob.get(*position..*position + 4).ok_or(truncated)?;
+    let length = u32::from_be_bytes(length_bytes.try_into().unwrap()) as usize;
+    let start = *position + 4;
+    let field = blob
+        .get(start..start.saturating_add(length))
+        .ok_or(SshKeyError::Truncated(*position))?;
+    *position = start + length;
+    Ok(field)
+}
+
+/// Bit length of an SSH `mpint`, ignoring the sign-padding zero bytes.
+fn mpint_bits(mpint: &[u8]) -> usize {
+    let significant = match mpint.iter().position(|b| *b != 0) {
+        Some(start) => &mpint[start..],
+        None => return 0,
+    };
+    significant.len() * 8 - significant[0].leading_zeros() as usize
+}
+
+/// Splits a decoded key blob into its key type and fields, checking that the
+/// fields fit the key type exactly.
+pub fn parse_blob(blob: &[u8]) -> Result<(String, Vec<Vec<u8>>), SshKeyError> {
+    let mut position = 0;
+    let key_type = String::from_utf8_lossy(read_string(blob, &mut position)?).into_owned();
+    let (_, field_names, _) =
+        key_type_entry(&key_type).ok_or(SshKeyError::UnsupportedKeyType(key_type.clone()))?;
+    let fields = field_names
+        .iter()
+        .map(|_| read_string(blob, &mut position).map(<[u8]>::to_vec))
+        .collect::<Result<Vec<_>, _>>()?;
+    if position != blob.len() {
+        return Err(SshKeyError::TrailingBytes(blob.len() - position));
+    }
+    Ok((key_type, fields))
+}
+
+fn validate_fields(key_type: &str, fields: &[Vec<u8>]) -> Result<(), SshKeyError> {
+    if key_type.contains("ed25519") && fields[0].len() != ED25519_KEY_LENGTH {
+        return Err(SshKeyError::InvalidField("pk"));
+    }
+    if key_type.contains("ecdsa") {
+        // the curve must repeat the suffix of the key type, e.g. "nistp256"
+        let curve = String::from_utf8_lossy(&fields[0]);
+        let expected = key_type.trim_end_matches("@openssh.com");
+        if !expected.ends_with(&*curve) || !curve.starts_with("nistp") {
+            return Err(SshKeyError::InvalidField("curve"));
+        }
+    }
+    Ok(())
+}
+
+impl PublicKey {
+    pub fn field_names(&self) -> &'static [&'static str] {
+        key_type_entry(&self.key_type).map_or(&[], |(_, names, _)| names)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&[u8]> {
+        let index = self.field_names().iter().position(|n| *n == name)?;
+        self.fields.get(index).map(Vec::as_slice)
+    }
+
+    /// The label `ssh-keygen -l` shows in parentheses, e.g. `ED25519`.
+    pub fn label(&self) -> &'static str {
+        key_type_entry(&self.key_type).map_or("UNKNOWN", |(_, _, label)| label)
+    }
+
+    /// Key size in bits as `ssh-keygen -l` reports it.
+    pub fn bits(&self) -> usize {
+        match self.key_type.as_str() {
+            "ssh-rsa" => mpint_bits(&self.fields[1]),
+            "ssh-dss" => mpint_bits(&self.fields[0]),
+            key_type if key_type.contains("ed25519") => 256,
+            _ => {
+                let curve = String::from_utf8_lossy(&self.fields[0]);
+                curve.trim_start_matches("nistp").parse().unwrap_or(0)
+            }
+        }
+    }
+
+    /// `SHA256:` followed by the unpadded base64 of the blob's digest, as OpenSSH prints it.
+    pub fn fingerprint(&self) -> String {
+        let digest = Sha256::digest(&self.blob);
+        let encoded = encode_to_vec(&Base64Config::standard(), &digest);
+        let unpadded = encoded.strip_suffix(b"=").unwrap_or(&encoded);
+        format!("SHA256:{}", String::from_utf8_lossy(unpadded))
+    }
+}
+
+/// Splits off the first token, keeping quoted sections (which may contain
+/// spaces) together the way sshd does for authorized_keys options.
+fn split_token(line: &str) -> (&str, &str) {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ' ' | '\t' if !quoted => return (&line[..i], line[i..].trim_start()),
+            _ => {}
+        }
+    }
+    (line, "")
+}
+
+/// Parses one `.pub` or authorized_keys line:
+/// `[options] <key type> <base64 blob> [comment]`.
+pub fn parse_line(line: &str) -> Result<PublicKey, SshKeyError> {
+    let (first, rest) = split_token(line.trim());
+    let (options, key_type, rest) = if key_type_entry(first).is_some() {
+        (None, first, rest)
+    } else {
+        let (second, rest) = split_token(rest);
+        if key_type_entry(second).is_none() {
+            return Err(SshKeyError::UnsupportedKeyType(first.to_string()));
+        }
+        (Some(first.to_string()), second, rest)
+    };
+    let (data, comment) = split_token(rest);
+    if data.is_empty() {
+        return Err(SshKeyError::MissingKeyData);
+    }
+    let blob = decode_to_vec(&Base64Config::standard(), data.as_bytes())
+        .map_err(SshKeyError::InvalidBase64)?;
+    let (inner_key_type, fields) = parse_blob(&blob)?;
+    if inner_key_type != key_type {
+        return Err(SshKeyError::KeyTypeMismatch(
+            key_type.to_string(),
+            inner_key_type,
+        ));
+    }
+    validate_fields(key_type, &fields)?;
+    Ok(PublicKey {
+        options,
+        key_type: inner_key_type,
+        fields,
+        blob,
+        comment: (!comment.is_empty()).then(|| comment.to_string()),
+    })
+}
+
+/// Parses every key in an authorized_keys file, skipping blank lines and
+/// `#` comments. Like sshd, a bad line does not stop the others from being
+/// read; each result comes with its 1-based line number.
+pub fn parse_authorized_keys(text: &str) -> Vec<(usize, Result<PublicKey, SshKeyError>)> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(i, line)| (i + 1, parse_line(line)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+use super::*;
+
+// generated with ssh-keygen 9.2; fingerprints from `ssh-keygen -lf`
+const ED25519_KEY: &str =
+    "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIHLHSGLBN7/y8T4vA/G3vXDzvxYe01iLCrHbBZbf4z6K user@ed25519";
+const ECDSA_KEY: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBGx43lm3wS/D5HxmaHPM0NR3cykLIU063krCZaJyICJz3rlWr6lGgwn8mxXg5DYrOrnkW2xPrQPwbyo/9YA2gqw= user@ecdsa";
+const RSA_KEY: &str = "ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAgQDW9Rx4cDtArfpKPco42nlVFN5LqQMv8bafCFuCecleteBp6oiIOLoQVREZOtWwdpcdE6bYob9V+ld8UYdd+msmcBx4px1ILFd+OwJkgMOj6srtN0H/qBLL5vDasfEkvG12oGtJ8ZvjCxZK42w2021/V/aHoVmysX5zqOXlrlz54w==";
+
+#[test]
+fn test_ed25519_key() {
+    let key = parse_line(ED25519_KEY).expect("valid key");
+    assert_eq!(key.key_type, "ssh-ed25519");
+    assert_eq!(key.field("pk").map(<[u8]>::len), Some(32));
+    assert_eq!(key.comment.as_deref(), Some("user@ed25519"));
+    assert_eq!(key.options, None);
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.label(), "ED25519");
+    assert_eq!(
+        key.fingerprint(),
+        "SHA256:0xKQtRTAFD2D4BPO/JI4On+kGmVVgysRSjbjZ0IsF4s"
+    );
+}
+
+#[test]
+fn test_ecdsa_key() {
+    let key = parse_line(ECDSA_KEY).expect("valid key");
+    assert_eq!(key.field("curve"), Some(&b"nistp256"[..]));
+    assert_eq!(key.field("Q").map(<[u8]>::len), Some(65));
+    assert_eq!(key.bits(), 256);
+    assert_eq!(key.label(), "ECDSA");
+    assert_eq!(
+        key.fingerprint(),
+        "SHA256:LnT/XUpwTrTz6UAGnIvcxfyi6eljBlVgsFZp6Vr85Z0"
+    );
+}
+
+#[test]
+fn test_rsa_key_without_comment() {
+    let key = parse_line(RSA_KEY).expect("valid key");
+    assert_eq!(key.field("e"), Some(&[1u8, 0, 1][..]));
+    assert_eq!(key.comment, None);
+    assert_eq!(key.bits(), 1024);
+    assert_eq!(key.label(), "RSA");
+    assert_eq!(
+        key.fingerprint(),
+        "SHA256:YemzA+R7q1W7hmsYU9/nNjiPPVNW2580SCKyJdwquw8"
+    );
+}
+
+#[test]
+fn test_authorized_keys_with_options_and_comments() {
+    let text = format!(
+        "# team keys\n\n{ED25519_KEY}\nno-pty,command=\"echo a b\" {ECDSA_KEY}\nssh-foo AAAA\n"
+    );
+    let keys = parse_authorized_keys(&text);
+    assert_eq!(keys.len(), 3);
+    assert_eq!(keys[0].0, 3);
+    assert_eq!(keys[0].1.as_ref().unwrap().key_type, "ssh-ed25519");
+    let (line, key) = &keys[1];
+    assert_eq!(*line, 4);
+    let key = key.as_ref().expect("options are skipped");
+    assert_eq!(key.options.as_deref(), Some("no-pty,command=\"echo a b\""));
+    assert_eq!(key.comment.as_deref(), Some("user@ecdsa"));
+    match &keys[2] {
+        (5, Err(SshKeyError::UnsupportedKeyType(key_type))) if key_type == "ssh-foo" => {}
+        (line, other) => panic!(
+            "expected UnsupportedKeyType on line 5, got {line}: {:?}",
+            other.as_ref().err()
+        ),
+    }
+}
+
+#[test]
+fn test_key_type_mismatch() {
+    let data = ED25519_KEY.split(' ').nth(1).unwrap();
+    match parse_line(&format!("ssh-rsa {data}")) {
+        Err(SshKeyError::KeyTypeMismatch(outer, inner))
+            if outer == "ssh-rsa" && inner == "ssh-ed25519" => {}
+        other => panic!("expected KeyTypeMismatch, got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_malformed_blobs() {
+    let key = parse_line(ED25519_KEY).unwrap();
+    let encode = |blob: &[u8]| {
+        let data = encode_to_vec(&Base64Config::standard(), blob);
+        format!("ssh-ed25519 {}", String::from_utf8_lossy(&data))
+    };
+
+    match parse_line(&encode(&key.blob[..key.blob.len() - 1])) {
+        Err(SshKeyError::Truncated(15)) => {}
+        other => panic!("expected Truncated(15), got {:?}", other.err()),
+    }
+    let mut trailing = key.blob.clone();
+    trailing.extend_from_slice(b"xy");
+    match parse_line(&encode(&trailing)) {
+        Err(SshKeyError::TrailingBytes(2)) => {}
+        other => panic!("expected TrailingBytes(2), got {:?}", other.err()),
+    }
+    // a 31 byte ed25519 key
+    let mut short = b"\0\0\0\x0bssh-ed25519\0\0\0\x1f".to_vec();
+    short.extend_from_slice(&[7u8; 31]);
+    match parse_line(&encode(&short)) {
+        Err(SshKeyError::InvalidField("pk")) => {}
+        other => panic!("expected InvalidField(pk), got {:?}", other.err()),
+    }
+}
+
+#[test]
+fn test_line_errors() {
+    assert!(matches!(
+        parse_line("ssh-ed25519"),
+        Err(SshKeyError::MissingKeyData)
+    ));
+    assert!(matches!(
+        parse_line("ssh-ed25519 AAAA!"),
+        Err(SshKeyError::InvalidBase64(Base64Error::InvalidCharacter(
+            b'!'
+        )))
+    ));
+}
+
+#[test]
+fn test_mpint_bits() {
+    assert_eq!(mpint_bits(&[]), 0);
+    assert_eq!(mpint_bits(&[0x00, 0x80, 0x00]), 16);
+    assert_eq!(mpint_bits(&[0x01, 0x00, 0x01]), 17);
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", SshKeyError::MissingKeyData).contains("No base64"));
+    assert!(format!("{}", SshKeyError::UnsupportedKeyType("ssh-foo".into())).contains("ssh-foo"));
+    let e = SshKeyError::InvalidBase64(Base64Error::InvalidCharacter(b'!'));
+    assert!(format!("{e}").contains("Invalid character"));
+    assert!(format!("{}", SshKeyError::Truncated(19)).contains("offset 19"));
+    assert!(format!("{}", SshKeyError::TrailingBytes(2)).contains("2 unexpected"));
+    let e = SshKeyError::KeyTypeMismatch("ssh-rsa".into(), "ssh-ed25519".into());
+    assert!(format!("{e}").contains("ssh-ed25519"));
+    assert!(format!("{}", SshKeyError::InvalidField("pk")).contains("\"pk\""));
+}
@@ -0,0 +1,177 @@
+use crate::base64::{
+    Base64Config, Base64Error, Padding, decode_symbol, decode_to_vec, encode_byte, encode_to_vec,
+};
+use std::error;
+
+const BYTES_PER_LINE: usize = 45;
+const DEFAULT_MODE: u32 = 0o644;
+
+#[derive(Clone, Copy)]
+pub enum UuVariant {
+    /// Classic uuencode over `' '..='_'`, writing '`' in place of ' '
+    Uuencode,
+    /// xxencode over `+`, `-`, `0-9`, `A-Z`, `a-z`
+    Xxencode,
+}
+
+pub struct UuFile {
+    pub mode: u32,
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum UuError {
+    MissingBeginLine,
+    InvalidBeginLine(String),
+    InvalidCharacter(usize, u8),
+    MissingEndLine,
+}
+
+impl error::Error for UuError {}
+
+impl std::fmt::Display for UuError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UuError::MissingBeginLine => write!(f, "No \"begin\" line found"),
+            UuError::InvalidBeginLine(line) => {
+                write!(f, "Invalid \"begin\" line \"{}\"", line)
+            }
+            UuError::InvalidCharacter(line_number, c) => {
+                write!(
+                    f,
+                    "Invalid character \'{}\' in line {}",
+                    *c as char, *line_number
+                )
+            }
+            UuError::MissingEndLine => write!(f, "No \"end\" line found"),
+        }
+    }
+}
+
+impl UuFile {
+    pub fn new(name: String, data: Vec<u8>) -> Self {
+        UuFile {
+            mode: DEFAULT_MODE,
+            name,
+            data,
+        }
+    }
+}
+
+impl UuVariant {
+    fn config(&self) -> Base64Config {
+        let ranges = match self {
+            UuVariant::Uuencode => vec![b' '..=b'_'],
+            UuVariant::Xxencode => vec![
+                b'+'..=b'+',
+                b'-'..=b'-',
+                b'0'..=b'9',
+                b'A'..=b'Z',
+                b'a'..=b'z',
+            ],
+        };
+        Base64Config::new(ranges, Padding::NoPadding).unwrap()
+    }
+
+    /// Trailing spaces do not survive mail transport, so uuencode writes '`' instead.
+    fn wire_symbol(&self, symbol: u8) -> u8 {
+        match (self, symbol) {
+            (UuVariant::Uuencode, b' ') => b'`',
+            _ => symbol,
+        }
+    }
+
+    fn config_symbol(&self, symbol: u8) -> u8 {
+        match (self, symbol) {
+            (UuVariant::Uuencode, b'`') => b' ',
+            _ => symbol,
+        }
+    }
+}
+
+fn encode_line(variant: UuVariant, config: &Base64Config, chunk: &[u8], out: &mut Vec<u8>) {
+    let num_symbols = chunk.len().div_ceil(3) * 4;
+    let mut symbols = encode_to_vec(config, chunk);
+    symbols.resize(num_symbols, encode_byte(config, 0));
+    out.push(variant.wire_symbol(encode_byte(config, chunk.len() as u8)));
+    out.extend(
+        symbols
+            .into_iter()
+            .map(|symbol| variant.wire_symbol(symbol)),
+    );
+    out.push(b'\n');
+}
+
+fn decode_line(
+    variant: UuVariant,
+    config: &Base64Config,
+    line: &[u8],
+    line_number: usize,
+    out: &mut Vec<u8>,
+) -> Result<usize, UuError> {
+    let to_uu_error = |e| match e {
+        Base64Error::InvalidCharacter(c) => UuError::InvalidCharacter(line_number, c),
+        _ => unreachable!("unpadded configs only report invalid characters"),
+    };
+    let Some((length_symbol, symbols)) = line.split_first() else {
+        return Ok(0);
+    };
+    let length =
+        decode_symbol(config, variant.config_symbol(*length_symbol)).map_err(to_uu_error)?;
+    let length = length as usize;
+    // encoders may strip trailing zero symbols or append a checksum symbol
+    let mut symbols: Vec<u8> = symbols.iter().map(|s| variant.config_symbol(*s)).collect();
+    symbols.resize(length.div_ceil(3) * 4, encode_byte(config, 0));
+    let decoded = decode_to_vec(config, &symbols).map_err(to_uu_error)?;
+    out.extend(&decoded[..length]);
+    Ok(length)
+}
+
+fn parse_begin_line(line: &str) -> Option<(u32, String)> {
+    let rest = line.strip_prefix("begin ")?;
+    let (mode, name) = rest.split_once(' ')?;
+    let mode = u32::from_str_radix(mode, 8).ok()?;
+    Some((mode, name.to_string()))
+}
+
+pub fn encode_file(variant: UuVariant, file: &UuFile) -> Vec<u8> {
+    let config = variant.config();
+    let mut out = format!("begin {:o} {}\n", file.mode, file.name).into_bytes();
+    for chunk in file.data.chunks(BYTES_PER_LINE) {
+        encode_line(variant, &config, chunk, &mut out);
+    }
+    encode_line(variant, &config, &[], &mut out);
+    out.extend(b"end\n");
+    out
+}
+
+pub fn decode_file(variant: UuVariant, uu_encoded_bytes: &[u8]) -> Result<UuFile, UuError> {
+    let config = variant.config();
+    let mut lines = uu_encoded_bytes
+        .split(|b| *b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .enumerate()
+        .map(|(i, line)| (i + 1, line));
+    let (mode, name) = loop {
+        let (_, line) = lines.next().ok_or(UuError::MissingBeginLine)?;
+        if line.starts_with(b"begin ") {
+            let line = String::from_utf8_lossy(line);
+            break parse_begin_line(&line).ok_or_else(|| UuError::InvalidBeginLine(line.into()))?;
+        }
+    };
+    let mut data = Vec::new();
+    loop {
+        let (line_number, line) = lines.next().ok_or(UuError::MissingEndLine)?;
+        if decode_line(variant, &config, line, line_number, &mut data)? == 0 {
+            break;
+        }
+    }
+    match lines.next() {
+        Some((_, line)) if line.trim_ascii_end() == b"end" => Ok(UuFile { mode, name, data }),
+        _ => Err(UuError::MissingEndLine),
+    }
+}
+
+#[cfg(test)]
+mod tests;
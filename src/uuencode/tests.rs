@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn test_uuencode_known_file() {
+    let file = UuFile::new("cat.txt".to_string(), b"Cat".to_vec());
+    let encoded = encode_file(UuVariant::Uuencode, &file);
+    assert_eq!(encoded, b"begin 644 cat.txt\n#0V%T\n`\nend\n");
+    let decoded = decode_file(UuVariant::Uuencode, &encoded).expect("decode should succeed");
+    assert_eq!(decoded.mode, 0o644);
+    assert_eq!(decoded.name, "cat.txt");
+    assert_eq!(decoded.data, b"Cat");
+}
+
+#[test]
+fn test_uuencode_zero_bytes_use_backtick() {
+    let file = UuFile::new("zeroes".to_string(), vec![0u8; 3]);
+    let encoded = encode_file(UuVariant::Uuencode, &file);
+    assert_eq!(encoded, b"begin 644 zeroes\n#````\n`\nend\n");
+    // a space encoder writes the same data with ' ', which must decode identically
+    let decoded = decode_file(UuVariant::Uuencode, b"begin 644 zeroes\n#    \n \nend\n")
+        .expect("decode should succeed");
+    assert_eq!(decoded.data, [0u8; 3]);
+}
+
+#[test]
+fn test_xxencode_known_file() {
+    let file = UuFile {
+        mode: 0o755,
+        name: "cat.txt".to_string(),
+        data: b"Cat".to_vec(),
+    };
+    let encoded = encode_file(UuVariant::Xxencode, &file);
+    assert_eq!(encoded, b"begin 755 cat.txt\n1Eq3o\n+\nend\n");
+    let decoded = decode_file(UuVariant::Xxencode, &encoded).expect("decode should succeed");
+    assert_eq!(decoded.mode, 0o755);
+    assert_eq!(decoded.data, b"Cat");
+}
+
+#[test]
+fn test_line_wrapping_and_roundtrip() {
+    let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    for variant in [UuVariant::Uuencode, UuVariant::Xxencode] {
+        let file = UuFile::new("data bin".to_string(), data.clone());
+        let encoded = encode_file(variant, &file);
+        let lines: Vec<&[u8]> = encoded.split(|b| *b == b'\n').collect();
+        // begin, 22 full lines of 45 bytes, one of 10 bytes, the empty line, end, ""
+        assert_eq!(lines.len(), 1 + 23 + 1 + 1 + 1);
+        assert!(lines[1..23].iter().all(|line| line.len() == 61));
+        let decoded = decode_file(variant, &encoded).expect("decode should succeed");
+        assert_eq!(decoded.name, "data bin");
+        assert_eq!(decoded.data, data);
+    }
+}
+
+#[test]
+fn test_decode_tolerates_preamble_crlf_and_stripped_symbols() {
+    let encoded = b"From: someone\r\n\r\nbegin 600 cat.txt\r\n#0V%T\r\n`\r\nend\r\n";
+    let decoded = decode_file(UuVariant::Uuencode, encoded).expect("decode should succeed");
+    assert_eq!(decoded.mode, 0o600);
+    assert_eq!(decoded.data, b"Cat");
+
+    // trailing zero symbols stripped by a mail gateway
+    let decoded = decode_file(UuVariant::Uuencode, b"begin 644 z\n#\n\nend\n")
+        .expect("decode should succeed");
+    assert_eq!(decoded.data, [0u8; 3]);
+}
+
+#[test]
+fn test_decode_errors() {
+    match decode_file(UuVariant::Uuencode, b"#0V%T\n`\nend\n") {
+        Err(UuError::MissingBeginLine) => {}
+        other => panic!("expected MissingBeginLine, got {:?}", other.map(|f| f.name)),
+    }
+    match decode_file(UuVariant::Uuencode, b"begin 6x4 cat.txt\n`\nend\n") {
+        Err(UuError::InvalidBeginLine(line)) if line == "begin 6x4 cat.txt" => {}
+        other => panic!("expected InvalidBeginLine, got {:?}", other.map(|f| f.name)),
+    }
+    match decode_file(UuVariant::Uuencode, b"begin 644 cat.txt\n#0v%T\n`\nend\n") {
+        Err(UuError::InvalidCharacter(2, b'v')) => {}
+        other => panic!("expected InvalidCharacter, got {:?}", other.map(|f| f.name)),
+    }
+    match decode_file(UuVariant::Uuencode, b"begin 644 cat.txt\n#0V%T\n") {
+        Err(UuError::MissingEndLine) => {}
+        other => panic!("expected MissingEndLine, got {:?}", other.map(|f| f.name)),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", UuError::MissingBeginLine).contains("begin"));
+    assert!(format!("{}", UuError::InvalidBeginLine("begin x".into())).contains("begin x"));
+    assert!(format!("{}", UuError::InvalidCharacter(2, b'v')).contains("line 2"));
+    assert!(format!("{}", UuError::MissingEndLine).contains("end"));
+}
@@ -0,0 +1,354 @@
+use std::error;
+
+const DEFAULT_LINE_LENGTH: usize = 128;
+const OFFSET: u8 = 42;
+const ESCAPE: u8 = b'=';
+const ESCAPE_OFFSET: u8 = 64;
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+pub struct YencPart {
+    pub name: String,
+    pub size: u64,
+    pub part: Option<u32>,
+    pub total: Option<u32>,
+    pub begin: u64,
+    pub end: u64,
+    pub crc32: Option<u32>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub enum YencError {
+    MissingHeader(&'static str),
+    InvalidHeader(String),
+    SizeMismatch(u64, u64),
+    CrcMismatch(u32, u32),
+    MissingPart(u32),
+    InconsistentParts(String),
+}
+
+impl error::Error for YencError {}
+
+impl std::fmt::Display for YencError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            YencError::MissingHeader(keyword) => {
+                write!(f, "Missing \"{}\" line", keyword.trim_end())
+            }
+            YencError::InvalidHeader(line) => write!(f, "Invalid header \"{}\"", line),
+            YencError::SizeMismatch(expected, actual) => {
+                write!(f, "Expected {} bytes, decoded {}", *expected, *actual)
+            }
+            YencError::CrcMismatch(expected, actual) => {
+                write!(
+                    f,
+                    "CRC32 mismatch: expected {:08x}, computed {:08x}",
+                    *expected, *actual
+                )
+            }
+            YencError::MissingPart(part) => write!(f, "Missing part {}", *part),
+            YencError::InconsistentParts(reason) => {
+                write!(f, "Inconsistent parts: {}", reason)
+            }
+        }
+    }
+}
+
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// CRC-32 (IEEE 802.3) as used by the `crc32` and `pcrc32` trailer fields.
+pub fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, b| {
+        CRC32_TABLE[((crc ^ *b as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn needs_escape(encoded: u8, column: usize, is_last: bool) -> bool {
+    match encoded {
+        0 | b'\n' | b'\r' | ESCAPE => true,
+        b'\t' | b' ' => column == 0 || is_last,
+        b'.' => column == 0,
+        _ => false,
+    }
+}
+
+fn encode_body(bytes: &[u8], line_length: usize, out: &mut Vec<u8>) {
+    let mut column = 0;
+    for (i, b) in bytes.iter().enumerate() {
+        let encoded = b.wrapping_add(OFFSET);
+        let is_last = column + 1 >= line_length || i + 1 == bytes.len();
+        if needs_escape(encoded, column, is_last) {
+            out.push(ESCAPE);
+            out.push(encoded.wrapping_add(ESCAPE_OFFSET));
+            column += 2;
+        } else {
+            out.push(encoded);
+            column += 1;
+        }
+        if column >= line_length {
+            out.extend(b"\r\n");
+            column = 0;
+        }
+    }
+    if column != 0 {
+        out.extend(b"\r\n");
+    }
+}
+
+fn decode_line(line: &[u8], out: &mut Vec<u8>) {
+    // undo NNTP dot-stuffing
+    let line = if line.starts_with(b"..") {
+        &line[1..]
+    } else {
+        line
+    };
+    let mut symbols = line.iter();
+    while let Some(symbol) = symbols.next() {
+        let symbol = match *symbol {
+            ESCAPE => match symbols.next() {
+                Some(escaped) => escaped.wrapping_sub(ESCAPE_OFFSET),
+                None => break,
+            },
+            b'\r' | b'\n' => continue,
+            symbol => symbol,
+        };
+        out.push(symbol.wrapping_sub(OFFSET));
+    }
+}
+
+/// Header lines are `=ykeyword key=value ...`, with `name=` last and running
+/// to the end of the line so that it may contain spaces.
+struct Header<'a> {
+    line: &'a str,
+    fields: Vec<(&'a str, &'a str)>,
+    name: Option<&'a str>,
+}
+
+impl<'a> Header<'a> {
+    fn parse(line: &'a [u8], keyword: &'static str) -> Result<Self, YencError> {
+        let line = std::str::from_utf8(line)
+            .map_err(|_| YencError::InvalidHeader(String::from_utf8_lossy(line).into()))?
+            .trim_end();
+        let rest = line
+            .strip_prefix(keyword)
+            .ok_or(YencError::MissingHeader(keyword))?;
+        let (rest, name) = match rest.split_once(" name=") {
+            Some((rest, name)) => (rest, Some(name)),
+            None => (rest, None),
+        };
+        let fields = rest
+            .split_whitespace()
+            .filter_map(|field| field.split_once('='))
+            .collect();
+        Ok(Header { line, fields, name })
+    }
+
+    fn get<T: std::str::FromStr>(&self, key: &str) -> Result<Option<T>, YencError> {
+        match self.fields.iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => value
+                .parse()
+                .map(Some)
+                .map_err(|_| YencError::InvalidHeader(self.line.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    fn require<T: std::str::FromStr>(&self, key: &str) -> Result<T, YencError> {
+        self.get(key)?
+            .ok_or_else(|| YencError::InvalidHeader(self.line.to_string()))
+    }
+
+    fn crc(&self, key: &str) -> Result<Option<u32>, YencError> {
+        match self.fields.iter().find(|(k, _)| *k == key) {
+            Some((_, value)) => u32::from_str_radix(value, 16)
+                .map(Some)
+                .map_err(|_| YencError::InvalidHeader(self.line.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
+fn verify_crc(expected: Option<u32>, data: &[u8]) -> Result<(), YencError> {
+    match expected {
+        Some(expected) if expected != crc32(data) => {
+            Err(YencError::CrcMismatch(expected, crc32(data)))
+        }
+        _ => Ok(()),
+    }
+}
+
+pub struct YencFile {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+pub fn encode_file(name: &str, data: &[u8]) -> Vec<u8> {
+    let mut out = format!(
+        "=ybegin line={} size={} name={}\r\n",
+        DEFAULT_LINE_LENGTH,
+        data.len(),
+        name
+    )
+    .into_bytes();
+    encode_body(data, DEFAULT_LINE_LENGTH, &mut out);
+    out.extend(format!("=yend size={} crc32={:08x}\r\n", data.len(), crc32(data)).into_bytes());
+    out
+}
+
+/// Splits `data` into parts of at most `part_size` bytes, or a single empty
+/// part for empty `data`.
+///
+/// Panics if `part_size` is 0.
+pub fn encode_parts(name: &str, data: &[u8], part_size: usize) -> Vec<Vec<u8>> {
+    assert!(part_size > 0, "part size must not be 0");
+    let total = data.len().div_ceil(part_size).max(1);
+    let file_crc = crc32(data);
+    data.chunks(part_size)
+        .chain(data.is_empty().then_some(data))
+        .enumerate()
+        .map(|(i, chunk)| {
+            let begin = i * part_size + 1;
+            let end = begin + chunk.len() - 1;
+            let mut out = format!(
+                "=ybegin part={} total={} line={} size={} name={}\r\n=ypart begin={} end={}\r\n",
+                i + 1,
+                total,
+                DEFAULT_LINE_LENGTH,
+                data.len(),
+                name,
+                begin,
+                end
+            )
+            .into_bytes();
+            encode_body(chunk, DEFAULT_LINE_LENGTH, &mut out);
+            out.extend(
+                format!(
+                    "=yend size={} part={} pcrc32={:08x} crc32={:08x}\r\n",
+                    chunk.len(),
+                    i + 1,
+                    crc32(chunk),
+                    file_crc
+                )
+                .into_bytes(),
+            );
+            out
+        })
+        .collect()
+}
+
+pub fn decode_part(yenc_encoded_bytes: &[u8]) -> Result<YencPart, YencError> {
+    let mut lines = yenc_encoded_bytes
+        .split(|b| *b == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+        .skip_while(|line| !line.starts_with(b"=ybegin "));
+    let begin_header = Header::parse(lines.next().unwrap_or_default(), "=ybegin ")?;
+    let size: u64 = begin_header.require("size")?;
+    let name = begin_header
+        .name
+        .ok_or_else(|| YencError::InvalidHeader(begin_header.line.to_string()))?
+        .to_string();
+    let part: Option<u32> = begin_header.get("part")?;
+    let total: Option<u32> = begin_header.get("total")?;
+    let (begin, end) = match part {
+        Some(_) => {
+            let part_header = Header::parse(lines.next().unwrap_or_default(), "=ypart ")?;
+            let begin: u64 = part_header.require("begin")?;
+            // offsets are 1-based
+            if begin == 0 {
+                return Err(YencError::InvalidHeader(part_header.line.to_string()));
+            }
+            (begin, part_header.require("end")?)
+        }
+        None => (1, size),
+    };
+
+    let mut data = Vec::new();
+    let end_line = loop {
+        let line = lines.next().ok_or(YencError::MissingHeader("=yend "))?;
+        if line.starts_with(b"=yend ") {
+            break line;
+        }
+        decode_line(line, &mut data);
+    };
+    let end_header = Header::parse(end_line, "=yend ")?;
+    let part_size: u64 = end_header.require("size")?;
+    if part_size != data.len() as u64 {
+        return Err(YencError::SizeMismatch(part_size, data.len() as u64));
+    }
+    // begin is at least 1, and a range with begin == end + 1 is empty
+    let range_size = end.checked_sub(begin - 1);
+    if range_size != Some(part_size) {
+        return Err(YencError::SizeMismatch(range_size.unwrap_or(0), part_size));
+    }
+    let crc32 = end_header.crc("crc32")?;
+    match part {
+        Some(_) => verify_crc(end_header.crc("pcrc32")?, &data)?,
+        None => verify_crc(crc32, &data)?,
+    }
+    Ok(YencPart {
+        name,
+        size,
+        part,
+        total,
+        begin,
+        end,
+        crc32,
+        data,
+    })
+}
+
+pub fn reassemble(mut parts: Vec<YencPart>) -> Result<YencFile, YencError> {
+    let first = parts.first().ok_or(YencError::MissingHeader("=ybegin "))?;
+    let (name, size) = (first.name.clone(), first.size);
+    if parts.iter().any(|p| p.name != name || p.size != size) {
+        return Err(YencError::InconsistentParts(
+            "parts belong to different files".to_string(),
+        ));
+    }
+    if let Some(total) = parts.iter().find_map(|p| p.total)
+        && let Some(missing) = (1..=total).find(|n| !parts.iter().any(|p| p.part == Some(*n)))
+    {
+        return Err(YencError::MissingPart(missing));
+    }
+    // `size` comes from the headers, so check it before allocating for it
+    let data_len: usize = parts.iter().map(|p| p.data.len()).sum();
+    if data_len as u64 != size {
+        return Err(YencError::SizeMismatch(size, data_len as u64));
+    }
+    parts.sort_by_key(|p| p.begin);
+    let mut data = Vec::with_capacity(data_len);
+    for part in &parts {
+        if part.begin != data.len() as u64 + 1 || part.end > size {
+            return Err(YencError::InconsistentParts(format!(
+                "part starting at byte {} does not follow byte {}",
+                part.begin,
+                data.len()
+            )));
+        }
+        data.extend(&part.data);
+    }
+    verify_crc(parts.iter().find_map(|p| p.crc32), &data)?;
+    Ok(YencFile { name, data })
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,168 @@
+use super::*;
+
+fn body(bytes: &[u8], line_length: usize) -> Vec<u8> {
+    let mut out = Vec::new();
+    encode_body(bytes, line_length, &mut out);
+    out
+}
+
+#[test]
+fn test_crc32_check_value() {
+    assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    assert_eq!(crc32(b""), 0);
+}
+
+#[test]
+fn test_critical_characters_are_escaped() {
+    // these encode to NUL, LF, CR and '=' respectively
+    assert_eq!(body(&[0xD6, 0xE0, 0xE3, 0x13, 0x17], 128), b"=@=J=M=}A\r\n");
+}
+
+#[test]
+fn test_line_boundary_escapes() {
+    // '.' and ' ' at the start of a line, TAB at its end
+    assert_eq!(body(&[0x04, 0x04], 128), b"=n.\r\n");
+    assert_eq!(body(&[0xF6, 0x17, 0xDF], 128), b"=`A=I\r\n");
+    assert_eq!(body(&[0x17, 0xF6, 0x17], 2), b"A=`\r\nA\r\n");
+}
+
+#[test]
+fn test_decode_line_unescapes_and_undoes_dot_stuffing() {
+    let mut out = Vec::new();
+    decode_line(b"=@=J=M=}A", &mut out);
+    decode_line(b"..A", &mut out);
+    assert_eq!(out, [0xD6, 0xE0, 0xE3, 0x13, 0x17, 0x04, 0x17]);
+}
+
+#[test]
+fn test_single_part_roundtrip() {
+    let data: Vec<u8> = (0..=255u8).cycle().take(1000).collect();
+    let encoded = encode_file("my file.bin", &data);
+    assert!(encoded.starts_with(b"=ybegin line=128 size=1000 name=my file.bin\r\n"));
+    let part = decode_part(&encoded).expect("decode should succeed");
+    assert_eq!(part.name, "my file.bin");
+    assert_eq!(part.part, None);
+    assert_eq!((part.begin, part.end), (1, 1000));
+    assert_eq!(part.data, data);
+    let file = reassemble(vec![part]).expect("single part reassembles");
+    assert_eq!(file.data, data);
+}
+
+#[test]
+fn test_multipart_reassembly_out_of_order() {
+    let data: Vec<u8> = (0..5000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let encoded_parts = encode_parts("archive.bin", &data, 2000);
+    assert_eq!(encoded_parts.len(), 3);
+    let mut parts: Vec<YencPart> = encoded_parts
+        .iter()
+        .map(|p| decode_part(p).expect("part decodes"))
+        .collect();
+    assert_eq!(parts[2].part, Some(3));
+    assert_eq!((parts[2].begin, parts[2].end), (4001, 5000));
+    parts.reverse();
+    let file = reassemble(parts).expect("parts reassemble");
+    assert_eq!(file.name, "archive.bin");
+    assert_eq!(file.data, data);
+}
+
+#[test]
+fn test_empty_file_roundtrip() {
+    let part = decode_part(&encode_file("empty", b"")).expect("decode should succeed");
+    assert_eq!((part.begin, part.end), (1, 0));
+    let file = reassemble(vec![part]).expect("single part reassembles");
+    assert_eq!(file.data, b"");
+
+    let encoded_parts = encode_parts("empty", b"", 100);
+    assert_eq!(encoded_parts.len(), 1);
+    let part = decode_part(&encoded_parts[0]).expect("part decodes");
+    assert_eq!((part.part, part.total), (Some(1), Some(1)));
+    let file = reassemble(vec![part]).expect("empty part reassembles");
+    assert_eq!(file.data, b"");
+}
+
+#[test]
+#[should_panic(expected = "part size must not be 0")]
+fn test_encode_parts_rejects_zero_part_size() {
+    let _ = encode_parts("f", b"data", 0);
+}
+
+#[test]
+fn test_missing_part() {
+    let data = vec![7u8; 300];
+    let parts: Vec<YencPart> = encode_parts("f", &data, 100)
+        .iter()
+        .map(|p| decode_part(p).expect("part decodes"))
+        .filter(|p| p.part != Some(2))
+        .collect();
+    match reassemble(parts) {
+        Err(YencError::MissingPart(2)) => {}
+        other => panic!("expected MissingPart(2), got {:?}", other.map(|f| f.name)),
+    }
+}
+
+#[test]
+fn test_crc_mismatch() {
+    let mut encoded = encode_file("f", b"hello yenc");
+    // flip a data symbol on the second line
+    let position = encoded.iter().position(|b| *b == b'\n').unwrap() + 1;
+    encoded[position] ^= 1;
+    match decode_part(&encoded) {
+        Err(YencError::CrcMismatch(expected, _)) if expected == crc32(b"hello yenc") => {}
+        other => panic!("expected CrcMismatch, got {:?}", other.map(|p| p.name)),
+    }
+}
+
+#[test]
+fn test_header_errors() {
+    match decode_part(b"no headers here\r\n") {
+        Err(YencError::MissingHeader("=ybegin ")) => {}
+        other => panic!("expected MissingHeader, got {:?}", other.map(|p| p.name)),
+    }
+    match decode_part(b"=ybegin line=128 size=3 name=f\r\n]]]\r\n") {
+        Err(YencError::MissingHeader("=yend ")) => {}
+        other => panic!("expected MissingHeader, got {:?}", other.map(|p| p.name)),
+    }
+    match decode_part(b"=ybegin line=128 size=x name=f\r\n=yend size=0\r\n") {
+        Err(YencError::InvalidHeader(_)) => {}
+        other => panic!("expected InvalidHeader, got {:?}", other.map(|p| p.name)),
+    }
+    match decode_part(b"=ybegin line=128 size=4 name=f\r\n]]]\r\n=yend size=4\r\n") {
+        Err(YencError::SizeMismatch(4, 3)) => {}
+        other => panic!("expected SizeMismatch, got {:?}", other.map(|p| p.name)),
+    }
+}
+
+#[test]
+fn test_crafted_part_headers() {
+    // offsets are 1-based, and begin=0 would make end - begin + 1 overflow
+    let part = b"=ybegin part=1 line=128 size=3 name=f\r\n\
+                 =ypart begin=0 end=18446744073709551615\r\n]]]\r\n=yend size=3 part=1\r\n";
+    match decode_part(part) {
+        Err(YencError::InvalidHeader(_)) => {}
+        other => panic!("expected InvalidHeader, got {:?}", other.map(|p| p.name)),
+    }
+    let part = b"=ybegin part=1 line=128 size=3 name=f\r\n\
+                 =ypart begin=1 end=18446744073709551615\r\n]]]\r\n=yend size=3 part=1\r\n";
+    match decode_part(part) {
+        Err(YencError::SizeMismatch(u64::MAX, 3)) => {}
+        other => panic!("expected SizeMismatch, got {:?}", other.map(|p| p.name)),
+    }
+    // a huge size= is rejected before anything is allocated for it
+    let part = b"=ybegin part=1 line=128 size=18446744073709551615 name=f\r\n\
+                 =ypart begin=1 end=3\r\n]]]\r\n=yend size=3 part=1\r\n";
+    let part = decode_part(part).expect("the part itself is consistent");
+    match reassemble(vec![part]) {
+        Err(YencError::SizeMismatch(u64::MAX, 3)) => {}
+        other => panic!("expected SizeMismatch, got {:?}", other.map(|f| f.name)),
+    }
+}
+
+#[test]
+fn test_error_display() {
+    assert!(format!("{}", YencError::MissingHeader("=yend ")).contains("\"=yend\""));
+    assert!(format!("{}", YencError::InvalidHeader("=ybegin".into())).contains("Invalid header"));
+    assert!(format!("{}", YencError::SizeMismatch(4, 3)).contains("Expected 4"));
+    assert!(format!("{}", YencError::CrcMismatch(1, 2)).contains("00000001"));
+    assert!(format!("{}", YencError::MissingPart(2)).contains("part 2"));
+    assert!(format!("{}", YencError::InconsistentParts("x".into())).contains("Inconsistent"));
+}